@@ -0,0 +1,49 @@
+//! Timings for rendering glyphs, run with `cargo bench`.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use psf_rs::{Font, FontBuilder};
+
+fn display_glyph(c: &mut Criterion) {
+    let font = Font::load(include_bytes!("../test.psfu"));
+
+    c.bench_function("display_glyph 8x16", |b| {
+        b.iter(|| {
+            let mut sum = 0u32;
+            font.display_glyph(black_box('A'), |bit, x, y| {
+                sum += u32::from(bit) + u32::from(x) + u32::from(y);
+            });
+            sum
+        });
+    });
+
+    // A wide font with padding at the end of every row.
+    let glyphs = [0xa5; 5 * 32];
+    let mut raw = [0; 256];
+    let len = FontBuilder::new(36, 32, &glyphs)
+        .write_to(&mut raw)
+        .unwrap();
+    let wide = Font::load(&raw[..len]);
+
+    c.bench_function("display_glyph 36x32", |b| {
+        b.iter(|| {
+            let mut sum = 0u32;
+            wide.display_glyph(black_box(0), |bit, x, y| {
+                sum += u32::from(bit) + u32::from(x) + u32::from(y);
+            });
+            sum
+        });
+    });
+}
+
+fn glyph_rows(c: &mut Criterion) {
+    let font = Font::load(include_bytes!("../test.psfu"));
+
+    c.bench_function("glyph_rows 8x16", |b| {
+        b.iter(|| font.glyph_rows(black_box('A')).sum::<u32>());
+    });
+}
+
+criterion_group!(benches, display_glyph, glyph_rows);
+criterion_main!(benches);
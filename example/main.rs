@@ -3,13 +3,8 @@ use psf_rs::Font;
 fn main() {
     let font = Font::load(include_bytes!("../test.psfu"));
 
-    font.display_glyph('&', |bit, x, y| {
-        if x == 0 && y != 0 {
-            println!();
-        }
+    let mut art = String::new();
+    font.write_glyph_art('&', &mut art, '@', ' ').unwrap();
 
-        print!("{}", if bit == 1 { "@" } else { " " });
-    });
-
-    println!()
-}
\ No newline at end of file
+    print!("{art}");
+}
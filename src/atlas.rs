@@ -0,0 +1,100 @@
+//! Laying every glyph of a font out into one bitmap, for things like GPU textures.
+
+use alloc::{vec, vec::Vec};
+
+use crate::Font;
+
+/// Every glyph of a font arranged into a grid, with 1 byte per pixel.
+///
+/// Glyph `i` is in column `i % columns` and row `i / columns`,
+/// and cells that don't have a glyph are left blank.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Atlas {
+    /// The amount of glyphs in each row of the grid.
+    pub columns: u32,
+
+    /// The amount of rows in the grid.
+    pub rows: u32,
+
+    /// The width of each cell in pixels, which is the width of a glyph.
+    pub cell_width: u32,
+
+    /// The height of each cell in pixels, which is the height of a glyph.
+    pub cell_height: u32,
+
+    /// The pixels, row by row, where set bits are `0xff` and clear bits are `0x00`.
+    pub data: Vec<u8>,
+}
+
+impl Atlas {
+    /// Gets the width of the whole atlas in pixels.
+    #[must_use]
+    pub const fn width(&self) -> u32 {
+        self.columns * self.cell_width
+    }
+
+    /// Gets the height of the whole atlas in pixels.
+    #[must_use]
+    pub const fn height(&self) -> u32 {
+        self.rows * self.cell_height
+    }
+
+    /// Gets the pixel position of the top left corner of a glyph in the atlas.
+    ///
+    /// If [`Atlas::columns`] was set to 0, it's treated as 1, the same as in [`Font::build_atlas`].
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the glyph in the font, NOT a character.
+    #[must_use]
+    pub const fn position(&self, index: u32) -> (u32, u32) {
+        let columns = match self.columns {
+            0 => 1,
+            columns => columns,
+        };
+
+        (
+            (index % columns) * self.cell_width,
+            (index / columns) * self.cell_height,
+        )
+    }
+}
+
+impl<const N: usize> Font<'_, N> {
+    /// Lays every glyph out into a grid, in the order they're stored in.
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - How many glyphs wide the grid is. A value of 0 is treated as 1,
+    ///   and it's never wider than the amount of glyphs, since those columns would be empty.
+    #[must_use]
+    pub fn build_atlas(&self, columns: u32) -> Atlas {
+        let columns = columns.clamp(1, self.glyph_count().max(1));
+        let mut atlas = Atlas {
+            columns,
+            rows: self.header.length.div_ceil(columns),
+            cell_width: self.header.glyph_width,
+            cell_height: self.header.glyph_height,
+            data: Vec::new(),
+        };
+
+        let stride = atlas.width() as usize;
+        atlas.data = vec![0; stride * atlas.height() as usize];
+
+        for index in 0..self.header.length {
+            let Some(data) = self.glyph_data(index) else {
+                break;
+            };
+
+            let (left, top) = atlas.position(index);
+            let pixels = &mut atlas.data;
+
+            self.for_each_bit(data, |bit, x, y| {
+                pixels[(top as usize + y as usize) * stride + left as usize + x as usize] =
+                    bit * 0xff;
+            });
+        }
+
+        atlas
+    }
+}
@@ -0,0 +1,214 @@
+//! Writing psf2 fonts, so they can be made instead of just loaded.
+
+use crate::{
+    as_u32_le_bytes, error::WriteError, glyph_size_for, HEADER_SIZE, MAX_GLYPH_DIMENSION,
+    PSF2_MAGIC, PSF2_VERSION,
+};
+
+/// Builds a psf2 font out of glyph bitmaps and, optionally, a unicode table.
+///
+/// # Example
+///
+/// ```rust
+/// use psf_rs::{Font, FontBuilder};
+///
+/// // Two 8x2 glyphs, one blank and one full.
+/// let glyphs = [0x00, 0x00, 0xff, 0xff];
+/// let unicode = [(' ', 0), ('█', 1)];
+///
+/// let builder = FontBuilder::new(8, 2, &glyphs).unicode(&unicode);
+///
+/// let mut buf = [0; 64];
+/// let len = builder.write_to(&mut buf).unwrap();
+///
+/// let font = Font::load(&buf[..len]);
+/// assert_eq!(font.glyph_index('█' as u32), Some(1));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct FontBuilder<'a> {
+    /// The width of each glyph in pixels.
+    glyph_width: u32,
+
+    /// The height of each glyph in pixels.
+    glyph_height: u32,
+
+    /// The bitmaps of every glyph, one after another, with each row padded to a whole byte.
+    glyphs: &'a [u8],
+
+    /// Which characters map to which glyph indices, if there's a unicode table.
+    unicode: Option<&'a [(char, usize)]>,
+
+    /// Which sequences of characters map to which glyph indices.
+    sequences: &'a [(&'a [char], usize)],
+}
+
+impl<'a> FontBuilder<'a> {
+    /// Makes a builder for a font without a unicode table.
+    ///
+    /// # Arguments
+    ///
+    /// * `glyph_width` - The width of each glyph in pixels.
+    /// * `glyph_height` - The height of each glyph in pixels.
+    /// * `glyphs` - The bitmaps of every glyph, one after another, with each row padded to a whole byte.
+    #[must_use]
+    pub const fn new(glyph_width: u32, glyph_height: u32, glyphs: &'a [u8]) -> Self {
+        Self {
+            glyph_width,
+            glyph_height,
+            glyphs,
+            unicode: None,
+            sequences: &[],
+        }
+    }
+
+    /// Adds a unicode table to the font.
+    ///
+    /// # Arguments
+    ///
+    /// * `mappings` - Pairs of characters and the index of the glyph they map to.
+    #[must_use]
+    pub const fn unicode(mut self, mappings: &'a [(char, usize)]) -> Self {
+        self.unicode = Some(mappings);
+        self
+    }
+
+    /// Adds sequences of characters to the unicode table, see [`Font::sequence_index`](crate::Font::sequence_index).
+    /// This also adds a unicode table if there isn't one already.
+    ///
+    /// # Arguments
+    ///
+    /// * `sequences` - Pairs of character sequences and the index of the glyph they map to.
+    #[must_use]
+    pub const fn sequences(mut self, sequences: &'a [(&'a [char], usize)]) -> Self {
+        if self.unicode.is_none() {
+            self.unicode = Some(&[]);
+        }
+
+        self.sequences = sequences;
+        self
+    }
+
+    /// Gets the size of each glyph in bytes, or 0 if it's too big to fit in a `u32`.
+    const fn glyph_size(&self) -> usize {
+        match glyph_size_for(self.glyph_width, self.glyph_height) {
+            Some(size) => size as usize,
+            None => 0,
+        }
+    }
+
+    /// Gets the amount of bytes the finished font takes up.
+    #[must_use]
+    pub fn byte_len(&self) -> usize {
+        let Some(unicode) = self.unicode else {
+            return HEADER_SIZE + self.glyphs.len();
+        };
+
+        let length = self.glyphs.len() / self.glyph_size().max(1);
+        let singles: usize = unicode.iter().map(|(char, _)| char.len_utf8()).sum();
+        let sequences: usize = self
+            .sequences
+            .iter()
+            .map(|(sequence, _)| 1 + sequence.iter().map(|char| char.len_utf8()).sum::<usize>())
+            .sum();
+
+        HEADER_SIZE + self.glyphs.len() + singles + sequences + length
+    }
+
+    /// Writes the font into a buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The buffer to write the font into.
+    ///
+    /// Returns the amount of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// * [`WriteError::InvalidDimensions`] if the width or height is bigger than [`MAX_GLYPH_DIMENSION`].
+    /// * [`WriteError::InvalidGlyphData`] if `glyphs` isn't a whole number of glyphs.
+    /// * [`WriteError::TooManyGlyphs`] if the amount of glyphs doesn't fit in a u32.
+    /// * [`WriteError::InvalidMapping`] if a character maps to a glyph that doesn't exist.
+    /// * [`WriteError::BufferTooSmall`] if the font doesn't fit into `buf`.
+    pub fn write_to(&self, buf: &mut [u8]) -> Result<usize, WriteError> {
+        if self.glyph_width > MAX_GLYPH_DIMENSION || self.glyph_height > MAX_GLYPH_DIMENSION {
+            return Err(WriteError::InvalidDimensions);
+        }
+
+        let glyph_size = self.glyph_size();
+
+        if glyph_size == 0 || self.glyphs.len() % glyph_size != 0 {
+            return Err(WriteError::InvalidGlyphData);
+        }
+
+        let length = self.glyphs.len() / glyph_size;
+        let Ok(header_length) = u32::try_from(length) else {
+            return Err(WriteError::TooManyGlyphs);
+        };
+        let unicode = self.unicode.unwrap_or_default();
+
+        if unicode.iter().any(|(_, index)| *index >= length)
+            || self.sequences.iter().any(|(_, index)| *index >= length)
+        {
+            return Err(WriteError::InvalidMapping);
+        }
+
+        let byte_len = self.byte_len();
+        let Some(buf) = buf.get_mut(..byte_len) else {
+            return Err(WriteError::BufferTooSmall(byte_len));
+        };
+
+        let fields = [
+            PSF2_VERSION,
+            HEADER_SIZE as u32,
+            self.unicode.is_some() as u32,
+            header_length,
+            glyph_size as u32,
+            self.glyph_height,
+            self.glyph_width,
+        ];
+
+        buf[..4].copy_from_slice(&PSF2_MAGIC);
+        for (i, field) in fields.iter().enumerate() {
+            buf[4 + i * 4..8 + i * 4].copy_from_slice(&as_u32_le_bytes(*field));
+        }
+
+        buf[HEADER_SIZE..HEADER_SIZE + self.glyphs.len()].copy_from_slice(self.glyphs);
+
+        if self.unicode.is_some() {
+            let mut position = HEADER_SIZE + self.glyphs.len();
+
+            for glyph in 0..length {
+                for (char, _) in unicode.iter().filter(|(_, index)| *index == glyph) {
+                    position += char.encode_utf8(&mut buf[position..]).len();
+                }
+
+                for (sequence, _) in self.sequences.iter().filter(|(_, index)| *index == glyph) {
+                    buf[position] = 0xfe;
+                    position += 1;
+
+                    for char in *sequence {
+                        position += char.encode_utf8(&mut buf[position..]).len();
+                    }
+                }
+
+                buf[position] = 0xff;
+                position += 1;
+            }
+        }
+
+        Ok(byte_len)
+    }
+
+    /// Builds the font into a new vector.
+    ///
+    /// # Errors
+    ///
+    /// * The same as [`FontBuilder::write_to`], except that the buffer is never too small.
+    #[cfg(feature = "alloc")]
+    pub fn build(&self) -> Result<alloc::vec::Vec<u8>, WriteError> {
+        let mut buf = alloc::vec![0; self.byte_len()];
+        self.write_to(&mut buf)?;
+
+        Ok(buf)
+    }
+}
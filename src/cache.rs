@@ -0,0 +1,156 @@
+//! Keeping unpacked glyphs around, so the ones that get displayed constantly don't need to be unpacked every time.
+
+use crate::{Font, RenderError};
+
+/// A cache of up to `N` unpacked glyphs, which throws out the least recently used one when it's full.
+///
+/// Each glyph is stored with 1 byte per pixel, which is always 0 or 1, row by row.
+/// Glyphs can be at most `PIXELS` pixels in total, so the default is enough for 16x16 glyphs.
+///
+/// Glyphs are cached by their index, so a cache should only be used with one font.
+///
+/// # Example
+///
+/// ```rust
+/// use psf_rs::{Font, GlyphCache};
+///
+/// let font = Font::load(include_bytes!("../test.psfu"));
+/// let mut cache = GlyphCache::<32>::new();
+///
+/// let pixels = cache.get_or_render(&font, 'A').unwrap();
+/// assert_eq!(pixels.len(), 8 * 16);
+/// ```
+#[derive(Clone, Debug)]
+pub struct GlyphCache<const N: usize, const PIXELS: usize = 256> {
+    entries: [Entry<PIXELS>; N],
+
+    /// Goes up every time the cache is used, so entries know how long ago they were used.
+    clock: u64,
+}
+
+/// A single glyph in a [`GlyphCache`].
+#[derive(Clone, Debug)]
+struct Entry<const PIXELS: usize> {
+    /// The index of the glyph, or `None` if this entry hasn't been used yet.
+    index: Option<u32>,
+
+    /// The value of the clock when this entry was last used.
+    used: u64,
+
+    pixels: [u8; PIXELS],
+}
+
+impl<const N: usize, const PIXELS: usize> GlyphCache<N, PIXELS> {
+    /// Makes an empty cache.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: [const {
+                Entry {
+                    index: None,
+                    used: 0,
+                    pixels: [0; PIXELS],
+                }
+            }; N],
+            clock: 0,
+        }
+    }
+
+    /// Gets the unpacked pixels of the glyph a character is displayed with,
+    /// unpacking it first if it isn't cached already.
+    ///
+    /// # Arguments
+    ///
+    /// * `font` - The font the glyph comes from, which should always be the same one.
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    ///
+    /// Returns the pixels, which is `width * height` bytes,
+    /// or `None` if the glyph has more than `PIXELS` pixels, `N` is 0, or the font has no glyphs.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn get_or_render<T: TryInto<u32>, const M: usize>(
+        &mut self,
+        font: &Font<'_, M>,
+        char: T,
+    ) -> Option<&[u8]> {
+        let len = (font.header.glyph_width * font.header.glyph_height) as usize;
+        if len > PIXELS {
+            return None;
+        }
+
+        let index = match font.try_lookup_index(char, font.fallback) {
+            Ok((index, _)) => index,
+            Err(RenderError::GlyphDataTruncated) if font.header.length == 0 => return None,
+            Err(err) => panic!("{err}"),
+        };
+
+        self.clock += 1;
+
+        let slot = if let Some(slot) = self.position(index) {
+            slot
+        } else {
+            let slot = self.least_recently_used()?;
+            let entry = &mut self.entries[slot];
+            let width = font.header.glyph_width as usize;
+
+            entry.index = Some(index);
+            font.for_each_bit(font.glyph_data(index)?, |bit, x, y| {
+                entry.pixels[y as usize * width + x as usize] = bit;
+            });
+
+            slot
+        };
+
+        let entry = &mut self.entries[slot];
+        entry.used = self.clock;
+
+        Some(&entry.pixels[..len])
+    }
+
+    /// Gets how many glyphs are in the cache.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.index.is_some())
+            .count()
+    }
+
+    /// Checks whether the cache doesn't have any glyphs in it.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Throws out every glyph, like when switching to a different font.
+    pub fn clear(&mut self) {
+        for entry in &mut self.entries {
+            entry.index = None;
+        }
+    }
+
+    /// Finds the entry a glyph is in.
+    fn position(&self, index: u32) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.index == Some(index))
+    }
+
+    /// Finds the entry to put a new glyph in, which is either an unused one
+    /// or the one that was used the longest time ago.
+    fn least_recently_used(&self) -> Option<usize> {
+        self.entries
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, entry)| (entry.index.is_some(), entry.used))
+            .map(|(slot, _)| slot)
+    }
+}
+
+impl<const N: usize, const PIXELS: usize> Default for GlyphCache<N, PIXELS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
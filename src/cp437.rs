@@ -0,0 +1,54 @@
+//! The character set of the original IBM PC, which fonts without a unicode table usually follow.
+
+use crate::Font;
+
+/// The character each glyph is in code page 437, in order.
+///
+/// The first 32 are the symbols the IBM PC showed in place of control characters,
+/// rather than the control characters themselves.
+#[rustfmt::skip]
+const CP437: [char; 256] = [
+    '\0', '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼',
+    '►', '◄', '↕', '‼', '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼',
+    ' ', '!', '"', '#', '$', '%', '&', '\'', '(', ')', '*', '+', ',', '-', '.', '/',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', ':', ';', '<', '=', '>', '?',
+    '@', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O',
+    'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '[', '\\', ']', '^', '_',
+    '`', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o',
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '{', '|', '}', '~', '⌂',
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}',
+];
+
+impl<const N: usize> Font<'_, N> {
+    /// Gets the glyph index of a character, treating fonts without a unicode table as code page 437.
+    ///
+    /// Most fonts without a unicode table are laid out like the IBM PC's code page 437,
+    /// so this lets characters like `'╝'` be found in them even though [`Font::glyph_index`]
+    /// can only pass characters straight through.
+    /// Fonts that do have a unicode table just use it, the same as [`Font::glyph_index`].
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - The character you want the index of.
+    ///
+    /// Returns `None` if the character isn't in code page 437, isn't in the unicode table,
+    /// or the font doesn't have as many glyphs as code page 437 needs for it.
+    #[must_use]
+    pub fn cp437_index(&self, char: char) -> Option<usize> {
+        if self.header.flags.unicode {
+            return self.glyph_index(char as u32);
+        }
+
+        CP437
+            .iter()
+            .position(|candidate| *candidate == char)
+            .filter(|index| self.is_valid_index(*index))
+    }
+}
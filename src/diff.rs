@@ -0,0 +1,64 @@
+//! Comparing two versions of a font, like for checking that a patch only changed what it meant to.
+
+use crate::Font;
+
+/// One difference between two fonts, see [`Font::diff`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffEntry {
+    /// The glyph at the included index has a different bitmap, or is only in one of the fonts.
+    Glyph(usize),
+
+    /// A character resolves to a different glyph, or is only in one of the unicode tables.
+    Mapping {
+        /// The character that changed.
+        char: char,
+
+        /// The glyph it resolves to in the original font, if any.
+        old: Option<usize>,
+
+        /// The glyph it resolves to in the other font, if any.
+        new: Option<usize>,
+    },
+}
+
+impl<'a, const N: usize> Font<'a, N> {
+    /// Compares this font against another version of it.
+    ///
+    /// Glyphs are compared byte for byte, so this is meant for fonts with the same dimensions.
+    /// Changed glyphs come first, in order, followed by every character in either unicode table
+    /// that resolves differently with [`Font::glyph_index`].
+    /// The characters are in the same order as [`Font::unicode_entries`],
+    /// so they might come up more than once if a table hasn't been parsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The font to compare against, like a patched copy of this one.
+    pub fn diff<'b, const M: usize>(
+        &'b self,
+        other: &'b Font<'a, M>,
+    ) -> impl Iterator<Item = DiffEntry> + 'b {
+        let length = self.header.length.max(other.header.length);
+        let mapping = |char: char| {
+            let (old, new) = (
+                self.glyph_index(char as u32),
+                other.glyph_index(char as u32),
+            );
+            (old != new).then_some(DiffEntry::Mapping { char, old, new })
+        };
+
+        let glyphs = (0..length)
+            .filter(|index| self.glyph_data(*index) != other.glyph_data(*index))
+            .map(|index| DiffEntry::Glyph(index as usize));
+
+        // Characters that are in both tables are only checked once, from this one.
+        let changed = self
+            .unicode_entries()
+            .filter_map(move |(char, _)| mapping(char));
+        let added = other
+            .unicode_entries()
+            .filter(|(char, _)| self.glyph_index(*char as u32).is_none())
+            .filter_map(move |(char, _)| mapping(char));
+
+        glyphs.chain(changed).chain(added)
+    }
+}
@@ -0,0 +1,364 @@
+//! Fonts that are parsed at compile time, so there's nothing left to do when they're used.
+
+use crate::{decode_utf8, for_each_bit, BitOrder, GlyphResult, Header, RenderError, Version};
+
+/// A font that was parsed at compile time, usually with [`embed!`](crate::embed).
+///
+/// The unicode table is baked into a sorted array, so unlike [`Font`](crate::Font)
+/// there's no fixed capacity and nothing to parse at runtime.
+/// Everything lives in static data, which makes it a good fit for fonts stored in ROM.
+///
+/// # Example
+///
+/// ```rust
+/// use psf_rs::StaticFont;
+///
+/// const FONT: StaticFont = psf_rs::embed!("../test.psfu");
+///
+/// FONT.display_glyph('A', |bit, x, y| {
+///     // Stuff
+/// });
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct StaticFont<'a> {
+    /// The font header for this font.
+    pub header: Header,
+
+    /// The glyph data, without the header or the unicode table.
+    data: &'a [u8],
+
+    /// Pairs of codepoints and glyph indices, sorted by codepoint.
+    entries: &'a [(u32, u32)],
+
+    /// The character to display when one isn't in the font.
+    fallback: char,
+}
+
+impl<'a> StaticFont<'a> {
+    /// Makes a font out of its raw bytes and its unicode table,
+    /// which can be worked out with [`StaticFont::entries`].
+    ///
+    /// This is what [`embed!`](crate::embed) expands to, which is easier to use.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the font file itself. This can be either psf1 or psf2.
+    /// * `entries` - The unicode table from [`StaticFont::entries`].
+    ///
+    /// # Panics
+    ///
+    /// * If the font can't be loaded, see [`Font::try_load`](crate::Font::try_load) for the details.
+    ///   In a const context this is a compile error.
+    #[must_use]
+    pub const fn new(raw: &'a [u8], entries: &'a [(u32, u32)]) -> Self {
+        let (header, data, _) = split(raw);
+
+        Self {
+            header,
+            data,
+            entries,
+            fallback: '?',
+        }
+    }
+
+    /// Counts the characters in the unicode table of a font,
+    /// which is how long the array from [`StaticFont::entries`] has to be.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the font file itself.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`StaticFont::new`].
+    #[must_use]
+    pub const fn entry_count(raw: &[u8]) -> usize {
+        let (header, _, table) = split(raw);
+
+        collect_entries(&header, table, &mut [])
+    }
+
+    /// Parses the unicode table of a font into pairs of codepoints and glyph indices,
+    /// sorted by codepoint.
+    ///
+    /// Sequences are skipped, the same as they are in [`Font::glyph_index`](crate::Font::glyph_index).
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the font file itself.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`StaticFont::new`].
+    /// * If `LEN` isn't [`StaticFont::entry_count`].
+    #[must_use]
+    pub const fn entries<const LEN: usize>(raw: &[u8]) -> [(u32, u32); LEN] {
+        let (header, _, table) = split(raw);
+        let mut entries = [(0, 0); LEN];
+
+        assert!(
+            collect_entries(&header, table, &mut entries) == LEN,
+            "wrong length for the unicode table"
+        );
+
+        sort_entries(&mut entries, &mut [(0, 0); LEN]);
+        entries
+    }
+
+    /// Sets the character that's displayed when a character isn't in the font, which is `?` by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `fallback` - The character to display instead.
+    #[must_use]
+    pub const fn with_fallback(mut self, fallback: char) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Gets the glyph index of a character, the same as [`Font::glyph_index`](crate::Font::glyph_index).
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - The Unicode Scalar Value of the character you want the index of.
+    ///
+    /// Returns `None` if the character isn't in the unicode table.
+    #[must_use]
+    pub fn glyph_index(&self, char: u32) -> Option<usize> {
+        if !self.header.flags.unicode || char < 128 {
+            return Some(char as usize);
+        }
+
+        // The last mapping for a character wins, like it does in `Font`.
+        let end = self
+            .entries
+            .partition_point(|(codepoint, _)| *codepoint <= char);
+        let (codepoint, glyph) = self.entries.get(end.checked_sub(1)?)?;
+
+        (*codepoint == char).then_some(*glyph as usize)
+    }
+
+    /// Gets the raw bitmap of a glyph, the same as [`Font::glyph_data`](crate::Font::glyph_data).
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the glyph in the font, NOT a character.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    #[must_use]
+    pub fn glyph_data(&self, index: u32) -> Option<&'a [u8]> {
+        if index >= self.header.length {
+            return None;
+        }
+
+        let size = self.header.glyph_size as usize;
+        let from = size.checked_mul(index as usize)?;
+
+        self.data.get(from..from.checked_add(size)?)
+    }
+
+    /// Displays a glyph, the same as [`Font::display_glyph`](crate::Font::display_glyph).
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`](crate::Font::display_glyph).
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`](crate::Font::display_glyph).
+    pub fn display_glyph<T: TryInto<u32>>(
+        &self,
+        char: T,
+        action: impl FnMut(u8, u8, u8),
+    ) -> GlyphResult {
+        let Ok(char) = TryInto::<u32>::try_into(char) else {
+            panic!("{}", RenderError::InvalidCharacter);
+        };
+
+        let Some((data, result)) = [
+            (char, GlyphResult::Found),
+            (self.fallback as u32, GlyphResult::Fallback),
+        ]
+        .into_iter()
+        .filter_map(|(char, result)| Some((u32::try_from(self.glyph_index(char)?).ok()?, result)))
+        .chain([(0, GlyphResult::Missing)])
+        .find_map(|(index, result)| Some((self.glyph_data(index)?, result))) else {
+            panic!("{}", RenderError::GlyphDataTruncated);
+        };
+
+        for_each_bit(&self.header, data, BitOrder::MsbFirst, action);
+        result
+    }
+}
+
+/// Parses a font into its header, glyph data, and unicode table, panicking if it's malformed.
+const fn split(raw: &[u8]) -> (Header, &[u8], &[u8]) {
+    let Ok(header) = Header::parse_any(raw) else {
+        panic!("failed to parse font header");
+    };
+
+    assert!(
+        header.has_valid_dimensions(),
+        "header describes glyphs with invalid dimensions"
+    );
+
+    let Some(end) = header.checked_glyphs_end() else {
+        panic!("header describes glyphs with invalid dimensions");
+    };
+
+    let start = header.size as usize;
+    assert!(raw.len() >= end, "font data truncated");
+
+    let (glyphs, table) = raw.split_at(end);
+    let table = if header.flags.unicode { table } else { &[] };
+
+    (header, glyphs.split_at(start).1, table)
+}
+
+/// Walks the unicode table of a font, writing each character and its glyph into `out`
+/// for as long as there's room.
+///
+/// Returns how many characters there are in total, even the ones that didn't fit.
+const fn collect_entries(header: &Header, mut table: &[u8], out: &mut [(u32, u32)]) -> usize {
+    let mut count = 0;
+    let mut glyph = 0;
+    let mut in_sequence = false;
+
+    while glyph < header.length && !table.is_empty() {
+        let end = match header.format {
+            Version::Psf1 => {
+                let [low, high, ..] = *table else {
+                    break;
+                };
+
+                match u16::from_le_bytes([low, high]) {
+                    0xffff => {
+                        glyph += 1;
+                        in_sequence = false;
+                    }
+                    0xfffe => in_sequence = true,
+                    codepoint => {
+                        if !in_sequence && char::from_u32(codepoint as u32).is_some() {
+                            push_entry(out, &mut count, (codepoint as u32, glyph));
+                        }
+                    }
+                }
+
+                2
+            }
+            Version::Psf2 => {
+                let mut end = 0;
+                while end < table.len() && table[end] != 0xff && table[end] != 0xfe {
+                    end += 1;
+                }
+
+                let mut entry = table.split_at(end).0;
+                while let Some((len, codepoint)) = decode_utf8(entry) {
+                    if let (Some(codepoint), false) = (codepoint, in_sequence) {
+                        push_entry(out, &mut count, (codepoint, glyph));
+                    }
+
+                    entry = entry.split_at(len).1;
+                }
+
+                if end < table.len() {
+                    if table[end] == 0xff {
+                        glyph += 1;
+                        in_sequence = false;
+                    } else {
+                        in_sequence = true;
+                    }
+
+                    end += 1;
+                }
+
+                end
+            }
+        };
+
+        table = table.split_at(end).1;
+    }
+
+    count
+}
+
+/// Writes an entry into `out` if there's room, and counts it either way.
+const fn push_entry(out: &mut [(u32, u32)], count: &mut usize, entry: (u32, u32)) {
+    if *count < out.len() {
+        out[*count] = entry;
+    }
+
+    *count += 1;
+}
+
+/// Sorts entries by codepoint with a bottom up merge sort, which keeps entries
+/// for the same codepoint in the order they were in.
+///
+/// `scratch` has to be the same length as `entries`.
+const fn sort_entries(entries: &mut [(u32, u32)], scratch: &mut [(u32, u32)]) {
+    let len = entries.len();
+    let mut width = 1;
+
+    while width < len {
+        let mut start = 0;
+        while start < len {
+            let middle = min(start + width, len);
+            let end = min(start + width * 2, len);
+            let (mut left, mut right, mut i) = (start, middle, start);
+
+            while i < end {
+                if left < middle && (right >= end || entries[left].0 <= entries[right].0) {
+                    scratch[i] = entries[left];
+                    left += 1;
+                } else {
+                    scratch[i] = entries[right];
+                    right += 1;
+                }
+
+                i += 1;
+            }
+
+            start = end;
+        }
+
+        let mut i = 0;
+        while i < len {
+            entries[i] = scratch[i];
+            i += 1;
+        }
+
+        width *= 2;
+    }
+}
+
+/// `usize::min`, which isn't const.
+const fn min(a: usize, b: usize) -> usize {
+    if a < b {
+        a
+    } else {
+        b
+    }
+}
+
+/// Embeds a font into the program, parsing it at compile time into a [`StaticFont`].
+///
+/// The path is relative to the current file, the same as [`include_bytes!`].
+/// Fonts that can't be loaded are a compile error.
+///
+/// ```rust
+/// const FONT: psf_rs::StaticFont = psf_rs::embed!("../test.psfu");
+///
+/// assert_eq!(FONT.header.glyph_height, 16);
+/// ```
+#[macro_export]
+macro_rules! embed {
+    ($path:literal) => {{
+        const RAW: &[u8] = include_bytes!($path);
+        const ENTRIES: [(u32, u32); $crate::StaticFont::entry_count(RAW)] =
+            $crate::StaticFont::entries(RAW);
+        const FONT: $crate::StaticFont<'static> = $crate::StaticFont::new(RAW, &ENTRIES);
+
+        FONT
+    }};
+}
@@ -0,0 +1,204 @@
+use core::fmt;
+
+/// The reasons a font can fail to load.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoadError {
+    /// The input is too short to even contain a header.
+    TooShort,
+
+    /// The magic bytes don't match, so this probably isn't a psf2 font.
+    BadMagic,
+
+    /// The header states a version of psf2 this library doesn't know about, which is included.
+    UnsupportedVersion(u32),
+
+    /// The header says it's the included amount of bytes, which is smaller than a psf2 header.
+    InvalidHeaderSize(u32),
+
+    /// The header describes glyphs that can't exist, like ones that are zero bytes in size.
+    InvalidDimensions,
+
+    /// The input ends before all of the glyphs the header promises.
+    TruncatedGlyphData {
+        /// The minimum amount of bytes the header says the font needs.
+        expected: usize,
+
+        /// The amount of bytes that were actually given.
+        actual: usize,
+    },
+
+    /// The unicode table has more entries than there's room for, see [`UNICODE_CAPACITY`](crate::UNICODE_CAPACITY).
+    UnicodeTableFull,
+
+    /// The gzip data is corrupted or isn't gzip at all.
+    BadGzip,
+
+    /// The decompressed font doesn't fit into the scratch buffer.
+    ScratchTooSmall,
+
+    /// There's the included amount of bytes after the end of the font,
+    /// which is only an error when loading strictly, see [`LoadOptions::strict`](crate::LoadOptions::strict).
+    TrailingData(usize),
+}
+
+/// The reasons a font can fail to be written by a [`FontBuilder`](crate::FontBuilder).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WriteError {
+    /// The buffer is too small to hold the font, which needs the included amount of bytes.
+    BufferTooSmall(usize),
+
+    /// The glyph data isn't a whole number of glyphs, or the glyphs are zero bytes in size.
+    InvalidGlyphData,
+
+    /// A unicode mapping points past the last glyph.
+    InvalidMapping,
+
+    /// The glyphs are wider or taller than [`MAX_GLYPH_DIMENSION`](crate::MAX_GLYPH_DIMENSION),
+    /// so the font couldn't be loaded.
+    InvalidDimensions,
+
+    /// There are more glyphs than fit in the header.
+    TooManyGlyphs,
+}
+
+/// The reasons a glyph can fail to be displayed by [`Font::try_display_glyph`](crate::Font::try_display_glyph).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderError {
+    /// The character couldn't be converted into a u32.
+    InvalidCharacter,
+
+    /// There's no glyph data for the character, the fallback, or even the first glyph,
+    /// which happens when the font doesn't have any glyphs.
+    GlyphDataTruncated,
+}
+
+/// The reasons a glyph can fail to be replaced by [`Font::set_glyph`](crate::Font::set_glyph).
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditError {
+    /// There's no glyph at the index.
+    IndexOutOfBounds,
+
+    /// The bitmap isn't exactly one glyph long.
+    WrongLength {
+        /// The size of each glyph in the font.
+        expected: usize,
+
+        /// The length of the bitmap that was given.
+        actual: usize,
+    },
+}
+
+/// Things that don't stop a font from loading, but mean it probably isn't quite right.
+/// See [`Font::validate`](crate::Font::validate).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationWarning {
+    /// The glyphs are a different size than their width and height need.
+    GlyphSizeMismatch {
+        /// The size worked out from the width and height,
+        /// or `u32::MAX` if it's too big to fit in a `u32`.
+        expected: u32,
+
+        /// The size the header says the glyphs are.
+        actual: u32,
+    },
+
+    /// The unicode table doesn't have an entry for every glyph.
+    UnicodeEntriesMismatch {
+        /// The amount of glyphs in the font.
+        expected: u32,
+
+        /// The amount of entries in the unicode table.
+        actual: u32,
+    },
+
+    /// The header says there's a unicode table, but nothing is in it.
+    EmptyUnicodeTable,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort => write!(f, "font data is too short to contain a header"),
+            Self::BadMagic => write!(f, "header magic does not match, is this a psf font?"),
+            Self::UnsupportedVersion(version) => write!(f, "unsupported psf2 version {version}"),
+            Self::InvalidHeaderSize(size) => {
+                write!(f, "header size of {size} bytes is too small for psf2")
+            }
+            Self::InvalidDimensions => write!(f, "header describes glyphs with invalid dimensions"),
+            Self::TruncatedGlyphData { expected, actual } => write!(
+                f,
+                "font data truncated: expected at least {expected} bytes, got {actual}"
+            ),
+            Self::UnicodeTableFull => write!(f, "unicode table has too many entries"),
+            Self::BadGzip => write!(f, "gzip data is corrupted"),
+            Self::ScratchTooSmall => {
+                write!(f, "decompressed font does not fit in the scratch buffer")
+            }
+            Self::TrailingData(len) => {
+                write!(f, "{len} unexpected bytes after the end of the font")
+            }
+        }
+    }
+}
+
+impl core::error::Error for LoadError {}
+
+impl fmt::Display for WriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferTooSmall(needed) => {
+                write!(f, "buffer is too small: the font needs {needed} bytes")
+            }
+            Self::InvalidGlyphData => write!(f, "glyph data is not a whole number of glyphs"),
+            Self::InvalidMapping => write!(f, "unicode table maps to a glyph that does not exist"),
+            Self::InvalidDimensions => write!(f, "glyphs are too big to be displayed"),
+            Self::TooManyGlyphs => write!(f, "font has too many glyphs for a psf2 header"),
+        }
+    }
+}
+
+impl core::error::Error for WriteError {}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter => write!(f, "invalid character index"),
+            Self::GlyphDataTruncated => write!(f, "glyph index is out of bounds for this font"),
+        }
+    }
+}
+
+impl core::error::Error for RenderError {}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IndexOutOfBounds => write!(f, "glyph index is out of bounds for this font"),
+            Self::WrongLength { expected, actual } => write!(
+                f,
+                "bitmap should be {expected} bytes to fit a glyph, but is {actual}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for EditError {}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GlyphSizeMismatch { expected, actual } => write!(
+                f,
+                "glyphs should be {expected} bytes for their dimensions, but are {actual}"
+            ),
+            Self::UnicodeEntriesMismatch { expected, actual } => write!(
+                f,
+                "unicode table has {actual} entries, but there are {expected} glyphs"
+            ),
+            Self::EmptyUnicodeTable => write!(f, "unicode table is empty"),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Drawing glyphs with embedded-graphics, so fonts work with any of its display drivers.
+
+use embedded_graphics_core::{draw_target::DrawTarget, geometry::Point, Pixel};
+
+use crate::{Font, GlyphResult};
+
+impl<const N: usize> Font<'_, N> {
+    /// Draws a glyph into an embedded-graphics [`DrawTarget`], like a display.
+    ///
+    /// Only set bits are drawn, so whatever is already there shows through the clear ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `top_left` - Where the top left corner of the glyph goes on the target.
+    /// * `color` - The color set bits are drawn in.
+    /// * `target` - What the glyph is drawn into.
+    ///
+    /// Returns which glyph was drawn, the same as [`Font::display_glyph`].
+    ///
+    /// # Errors
+    ///
+    /// * Whatever error `target` gives when drawing.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn draw_glyph<T: TryInto<u32>, D: DrawTarget>(
+        &self,
+        char: T,
+        top_left: Point,
+        color: D::Color,
+        target: &mut D,
+    ) -> Result<GlyphResult, D::Error> {
+        let (data, result) = self.lookup(char);
+
+        target.draw_iter(
+            self.set_bits(data)
+                .map(|(x, y)| Pixel(top_left + Point::new(i32::from(x), i32::from(y)), color)),
+        )?;
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,404 @@
+//! A small gzip decompressor, so fonts can be loaded straight from `.psfu.gz` files.
+//!
+//! This inflates into a buffer given by the caller, so it doesn't need to allocate.
+
+use crate::{Font, LoadError, GZIP_MAGIC};
+
+/// The base lengths for each length symbol, starting at 257.
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+
+/// The amount of extra bits for each length symbol, starting at 257.
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+/// The base distances for each distance symbol.
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+
+/// The amount of extra bits for each distance symbol.
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// The order that the code length code lengths are stored in for dynamic blocks.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// A canonical huffman code.
+struct Huffman {
+    /// The amount of codes of each length.
+    counts: [u16; 16],
+
+    /// The symbols, ordered by their codes.
+    symbols: [u16; 288],
+}
+
+impl Huffman {
+    /// Builds a huffman code from the code length of each symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `lengths` - The code length of each symbol, where 0 means the symbol isn't used.
+    fn new(lengths: &[u8]) -> Result<Self, LoadError> {
+        let mut huffman = Self {
+            counts: [0; 16],
+            symbols: [0; 288],
+        };
+
+        for length in lengths {
+            huffman.counts[*length as usize] += 1;
+        }
+
+        // Codes can't take up more room than there is for their length.
+        let mut left = 1i32;
+        for count in &huffman.counts[1..] {
+            left = (left << 1) - *count as i32;
+            if left < 0 {
+                return Err(LoadError::BadGzip);
+            }
+        }
+
+        let mut offsets = [0; 16];
+        for length in 1..15 {
+            offsets[length + 1] = offsets[length] + huffman.counts[length];
+        }
+
+        for (symbol, length) in lengths.iter().enumerate() {
+            if *length != 0 {
+                huffman.symbols[offsets[*length as usize] as usize] = symbol as u16;
+                offsets[*length as usize] += 1;
+            }
+        }
+
+        Ok(huffman)
+    }
+}
+
+/// The state of the decompressor.
+struct Inflater<'a, 'b> {
+    /// The compressed deflate stream.
+    input: &'a [u8],
+
+    /// How far into `input` has been read.
+    position: usize,
+
+    /// Bits that have been read from `input` but not used yet.
+    bit_buffer: u32,
+
+    /// The amount of bits in `bit_buffer`.
+    bit_count: u32,
+
+    /// Where the decompressed data goes.
+    output: &'b mut [u8],
+
+    /// How much of `output` has been written.
+    written: usize,
+}
+
+impl Inflater<'_, '_> {
+    /// Reads a number from the next `count` bits, least significant bit first.
+    fn bits(&mut self, count: u32) -> Result<u32, LoadError> {
+        let mut value = self.bit_buffer;
+
+        while self.bit_count < count {
+            let byte = *self.input.get(self.position).ok_or(LoadError::BadGzip)?;
+            self.position += 1;
+
+            value |= (byte as u32) << self.bit_count;
+            self.bit_count += 8;
+        }
+
+        self.bit_buffer = value >> count;
+        self.bit_count -= count;
+
+        Ok(value & ((1 << count) - 1))
+    }
+
+    /// Writes a single byte to the output.
+    fn push(&mut self, byte: u8) -> Result<(), LoadError> {
+        *self
+            .output
+            .get_mut(self.written)
+            .ok_or(LoadError::ScratchTooSmall)? = byte;
+        self.written += 1;
+
+        Ok(())
+    }
+
+    /// Reads one symbol using a huffman code.
+    fn decode(&mut self, huffman: &Huffman) -> Result<u16, LoadError> {
+        let (mut code, mut first, mut index) = (0, 0, 0);
+
+        for count in &huffman.counts[1..] {
+            code |= self.bits(1)?;
+
+            let count = *count as u32;
+            if code < first + count {
+                return Ok(huffman.symbols[(index + code - first) as usize]);
+            }
+
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        Err(LoadError::BadGzip)
+    }
+
+    /// Copies a block that isn't compressed.
+    fn stored(&mut self) -> Result<(), LoadError> {
+        // Stored blocks start on a byte boundary.
+        self.bit_buffer = 0;
+        self.bit_count = 0;
+
+        let header = self
+            .input
+            .get(self.position..self.position + 4)
+            .ok_or(LoadError::BadGzip)?;
+        let length = u16::from_le_bytes([header[0], header[1]]);
+
+        if length != !u16::from_le_bytes([header[2], header[3]]) {
+            return Err(LoadError::BadGzip);
+        }
+
+        self.position += 4;
+
+        let data = self
+            .input
+            .get(self.position..self.position + length as usize)
+            .ok_or(LoadError::BadGzip)?;
+        self.output
+            .get_mut(self.written..self.written + length as usize)
+            .ok_or(LoadError::ScratchTooSmall)?
+            .copy_from_slice(data);
+
+        self.position += length as usize;
+        self.written += length as usize;
+
+        Ok(())
+    }
+
+    /// Decompresses a block using the given literal/length and distance codes.
+    fn codes(&mut self, lengths: &Huffman, distances: &Huffman) -> Result<(), LoadError> {
+        loop {
+            let symbol = self.decode(lengths)? as usize;
+
+            match symbol {
+                0..=255 => self.push(symbol as u8)?,
+                256 => return Ok(()),
+                _ => {
+                    let symbol = symbol - 257;
+                    if symbol >= LENGTH_BASE.len() {
+                        return Err(LoadError::BadGzip);
+                    }
+
+                    let length = LENGTH_BASE[symbol] as usize
+                        + self.bits(LENGTH_EXTRA[symbol] as u32)? as usize;
+
+                    let symbol = self.decode(distances)? as usize;
+                    if symbol >= DISTANCE_BASE.len() {
+                        return Err(LoadError::BadGzip);
+                    }
+
+                    let distance = DISTANCE_BASE[symbol] as usize
+                        + self.bits(DISTANCE_EXTRA[symbol] as u32)? as usize;
+
+                    if distance > self.written {
+                        return Err(LoadError::BadGzip);
+                    }
+
+                    // This is done byte by byte on purpose, since the copy can overlap itself.
+                    for _ in 0..length {
+                        self.push(self.output[self.written - distance])?;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Decompresses a block that uses the fixed huffman codes.
+    fn fixed(&mut self) -> Result<(), LoadError> {
+        let mut lengths = [0; 288];
+        lengths[..144].fill(8);
+        lengths[144..256].fill(9);
+        lengths[256..280].fill(7);
+        lengths[280..].fill(8);
+
+        self.codes(&Huffman::new(&lengths)?, &Huffman::new(&[5; 30])?)
+    }
+
+    /// Decompresses a block that comes with its own huffman codes.
+    fn dynamic(&mut self) -> Result<(), LoadError> {
+        let length_count = self.bits(5)? as usize + 257;
+        let distance_count = self.bits(5)? as usize + 1;
+        let code_count = self.bits(4)? as usize + 4;
+
+        if length_count > 286 || distance_count > 30 {
+            return Err(LoadError::BadGzip);
+        }
+
+        let mut lengths = [0; 286 + 30];
+        for index in &CODE_LENGTH_ORDER[..code_count] {
+            lengths[*index] = self.bits(3)? as u8;
+        }
+
+        let code_lengths = Huffman::new(&lengths[..19])?;
+        let total = length_count + distance_count;
+        let mut i = 0;
+
+        while i < total {
+            let symbol = self.decode(&code_lengths)?;
+
+            let (value, repeat) = match symbol {
+                0..=15 => (symbol as u8, 1),
+                16 if i > 0 => (lengths[i - 1], 3 + self.bits(2)?),
+                17 => (0, 3 + self.bits(3)?),
+                18 => (0, 11 + self.bits(7)?),
+                _ => return Err(LoadError::BadGzip),
+            };
+
+            let end = i + repeat as usize;
+            if end > total {
+                return Err(LoadError::BadGzip);
+            }
+
+            lengths[i..end].fill(value);
+            i = end;
+        }
+
+        // Without an end of block code, the block could never end.
+        if lengths[256] == 0 {
+            return Err(LoadError::BadGzip);
+        }
+
+        self.codes(
+            &Huffman::new(&lengths[..length_count])?,
+            &Huffman::new(&lengths[length_count..total])?,
+        )
+    }
+
+    /// Decompresses every block in the stream.
+    fn inflate(&mut self) -> Result<(), LoadError> {
+        loop {
+            let last = self.bits(1)? == 1;
+
+            match self.bits(2)? {
+                0 => self.stored()?,
+                1 => self.fixed()?,
+                2 => self.dynamic()?,
+                _ => return Err(LoadError::BadGzip),
+            }
+
+            if last {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Computes the CRC-32 of some data, which gzip uses to check the decompressed data.
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(u32::MAX, |crc, byte| {
+        (0..8).fold(crc ^ *byte as u32, |crc, _| {
+            (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
+
+/// Decompresses gzip data.
+///
+/// # Arguments
+///
+/// * `raw` - The gzip file.
+/// * `scratch` - Where the decompressed data gets written.
+///
+/// Returns the amount of bytes that were decompressed.
+fn decompress(raw: &[u8], scratch: &mut [u8]) -> Result<usize, LoadError> {
+    if raw.len() < 18 || raw[..2] != GZIP_MAGIC || raw[2] != 8 {
+        return Err(LoadError::BadGzip);
+    }
+
+    let flags = raw[3];
+    let mut position = 10;
+
+    // FEXTRA: extra data with its own length.
+    if flags & 0x04 != 0 {
+        let length = raw.get(position..position + 2).ok_or(LoadError::BadGzip)?;
+        position += 2 + u16::from_le_bytes([length[0], length[1]]) as usize;
+    }
+
+    // FNAME and FCOMMENT: zero terminated strings.
+    for flag in [0x08, 0x10] {
+        if flags & flag != 0 {
+            let rest = raw.get(position..).ok_or(LoadError::BadGzip)?;
+            position += rest
+                .iter()
+                .position(|x| x == &0)
+                .ok_or(LoadError::BadGzip)?
+                + 1;
+        }
+    }
+
+    // FHCRC: a checksum of the header, which isn't checked.
+    if flags & 0x02 != 0 {
+        position += 2;
+    }
+
+    let mut inflater = Inflater {
+        input: raw.get(position..).ok_or(LoadError::BadGzip)?,
+        position: 0,
+        bit_buffer: 0,
+        bit_count: 0,
+        output: scratch,
+        written: 0,
+    };
+
+    inflater.inflate()?;
+
+    let written = inflater.written;
+    let trailer = inflater
+        .input
+        .get(inflater.position..inflater.position + 8)
+        .ok_or(LoadError::BadGzip)?;
+
+    let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let size = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+
+    if crc != crc32(&inflater.output[..written]) || size != written as u32 {
+        return Err(LoadError::BadGzip);
+    }
+
+    Ok(written)
+}
+
+impl<'a> Font<'a> {
+    /// Loads a gzip compressed font, like the ones in /usr/share/kbd/consolefonts.
+    ///
+    /// The font is decompressed into `scratch`, which the font then borrows from,
+    /// so nothing gets allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the gzip file itself.
+    /// * `scratch` - A buffer big enough to hold the whole decompressed font.
+    ///
+    /// # Errors
+    ///
+    /// * [`LoadError::BadGzip`] if the gzip data is corrupted.
+    /// * [`LoadError::ScratchTooSmall`] if the decompressed font doesn't fit into `scratch`.
+    /// * Any of the errors from [`Font::try_load`].
+    pub fn load_gz(raw: &[u8], scratch: &'a mut [u8]) -> Result<Self, LoadError> {
+        let length = decompress(raw, scratch)?;
+        let scratch: &'a [u8] = scratch;
+
+        Self::try_load(&scratch[..length])
+    }
+}
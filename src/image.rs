@@ -0,0 +1,39 @@
+//! Turning glyphs into images from the image crate, so they can be saved or processed further.
+
+use alloc::vec;
+
+use ::image::GrayImage;
+
+use crate::{Atlas, Font};
+
+impl<const N: usize> Font<'_, N> {
+    /// Renders a glyph into a grayscale image, where set bits are 255 and clear bits are 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    #[must_use]
+    pub fn glyph_image<T: TryInto<u32>>(&self, char: T) -> GrayImage {
+        let (width, height) = (self.header.glyph_width, self.header.glyph_height);
+        let mut pixels = vec![0; width as usize * height as usize];
+        self.render_glyph_gray(char, &mut pixels, width as usize, 0xff, 0x00);
+
+        // The buffer is always the size of a glyph, so this never falls back to an empty image.
+        GrayImage::from_raw(width, height, pixels).unwrap_or_default()
+    }
+}
+
+impl Atlas {
+    /// Turns the atlas into a grayscale image, without copying the pixels.
+    ///
+    /// Returns `None` if [`Atlas::data`] isn't [`Atlas::width`] by [`Atlas::height`] pixels,
+    /// which can only happen if it was changed after [`Font::build_atlas`].
+    #[must_use]
+    pub fn into_image(self) -> Option<GrayImage> {
+        GrayImage::from_raw(self.width(), self.height(), self.data)
+    }
+}
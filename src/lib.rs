@@ -3,7 +3,7 @@
 //! The psfu format is what's used in the linux tty.
 //! You can find the built in psf2 fonts in /usr/share/kbd/consolefonts.
 //!
-//! This doesn't support the original psf.
+//! The original psf (psf1) is also supported, and gets loaded into the same structures.
 
 #![no_std]
 #![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
@@ -14,20 +14,139 @@
     clippy::cast_lossless
 )]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::panic;
 
+#[cfg(feature = "alloc")]
+mod atlas;
+mod builder;
+mod cache;
+#[cfg(feature = "cp437")]
+mod cp437;
+mod diff;
+mod embed;
+mod error;
+#[cfg(feature = "embedded-graphics")]
+mod graphics;
+#[cfg(feature = "gzip")]
+mod gzip;
+#[cfg(feature = "image")]
+mod image;
+#[cfg(feature = "alloc")]
+mod owned;
+mod render;
+mod stack;
 mod tests;
 
-type HashMap = heapless::IndexMap<[u8; 4], usize, hash32::BuildHasherDefault<ahash::AHasher>, 1024>;
+#[cfg(feature = "alloc")]
+pub use atlas::Atlas;
+pub use builder::FontBuilder;
+pub use cache::GlyphCache;
+pub use diff::DiffEntry;
+pub use embed::StaticFont;
+#[cfg(feature = "alloc")]
+pub use error::EditError;
+pub use error::{LoadError, RenderError, ValidationWarning, WriteError};
+#[cfg(feature = "alloc")]
+pub use owned::FontOwned;
+pub use stack::FontStack;
+
+/// The glyph data of a font, which can only be owned when `alloc` is enabled.
+#[cfg(feature = "alloc")]
+type Data<'a> = alloc::borrow::Cow<'a, [u8]>;
+
+/// The glyph data of a font, which can only be owned when `alloc` is enabled.
+#[cfg(not(feature = "alloc"))]
+type Data<'a> = &'a [u8];
+
+/// The unicode table of a font, as pairs of codepoints and glyph indices sorted by codepoint.
+///
+/// This has a fixed capacity so it works without an allocator,
+/// which is why it isn't sized from the amount of characters in the font.
+type UnicodeTable<const N: usize> = heapless::Vec<(u32, usize), N>;
+
+/// The maximum amount of characters a unicode table can have by default.
+///
+/// This can be changed per font with the `N` parameter of [`Font`].
+/// Fonts with any amount of characters can be used with an `N` of 0, which looks characters up
+/// in the raw table instead, or with a [`StaticFont`], which is sized for the font at compile time.
+pub const UNICODE_CAPACITY: usize = 1024;
+
+/// The amount of characters that can be kept in an [`IndexCache`].
+pub const INDEX_CACHE_SIZE: usize = 256;
+
+/// The maximum amount of characters in a sequence that can be looked up.
+pub const MAX_SEQUENCE: usize = 8;
+
+/// The biggest width or height a glyph can have, since glyphs are displayed with u8 coordinates.
+pub const MAX_GLYPH_DIMENSION: u32 = 256;
+
+/// Magic bytes that identify psf2, which are the first 4 bytes of [`Header::magic`].
+pub const PSF2_MAGIC: [u8; 4] = [0x72, 0xb5, 0x4a, 0x86];
+
+/// Magic bytes that identify psf1, which are the first 2 bytes of [`Header::magic`].
+pub const PSF1_MAGIC: [u8; 2] = [0x36, 0x04];
+
+/// Magic bytes that identify gzip.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// The only version of psf2 there is, and so the only one that can be loaded.
+pub const PSF2_VERSION: u32 = 0;
+
+/// The size of a psf2 header ignoring any extra padding.
+pub const HEADER_SIZE: usize = 32;
+
+/// The size of a psf1 header, which is always the same.
+const PSF1_HEADER_SIZE: usize = 4;
 
-/// Magic bytes that identify psf2.
-const MAGIC: [u8; 4] = [0x72, 0xb5, 0x4a, 0x86];
+/// The amount of glyphs that a tab lines up to in [`Font::display_str`].
+pub const TAB_WIDTH: u16 = 8;
+
+/// The version of the psf format a font was stored in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Version {
+    /// The original psf format, with a 4 byte header.
+    Psf1,
+
+    /// The newer psf2 format, with a 32 byte header.
+    Psf2,
+}
+
+impl Version {
+    /// Gets the bytes that end the entry for each glyph in the unicode table.
+    const fn entry_terminator(self) -> &'static [u8] {
+        match self {
+            Self::Psf1 => &[0xff, 0xff],
+            Self::Psf2 => &[0xff],
+        }
+    }
+}
+
+/// What kind of data a font file holds, from [`detect_format`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// An uncompressed psf1 font.
+    Psf1,
+
+    /// An uncompressed psf2 font.
+    Psf2,
+
+    /// Gzip compressed data, which is probably a font, see `Font::load_gz`.
+    Gzip,
+
+    /// Something else, or too short to tell.
+    Unknown,
+}
 
 /// Font flags.
 ///
 /// Currently, there is only one flag that specifies
 /// whether there is a unicode table or not.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Flags {
     /// Whether a unicode table is present or not.
     pub unicode: bool,
@@ -35,23 +154,37 @@ pub struct Flags {
 
 impl Flags {
     /// Parses the flags from four bytes.
-    const fn parse(raw: &[u8]) -> Self {
+    const fn parse(raw: [u8; 4]) -> Self {
         Self {
             unicode: raw[0] == 1,
         }
     }
+
+    /// Parses the flags from a psf1 mode byte.
+    ///
+    /// Both `PSF1_MODEHASTAB` and `PSF1_MODEHASSEQ` mean there's a unicode table.
+    const fn parse_psf1(mode: u8) -> Self {
+        Self {
+            unicode: mode & 0x06 != 0,
+        }
+    }
 }
 
 /// The font header.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Header {
     /// Magic that is consistent among all psfu files.
+    /// For psf1 fonts only the first two bytes are used, and the rest are 0.
     pub magic: [u8; 4],
 
-    /// The version of psfu used. Currently it's always 0.
+    /// Whether the font was stored as psf1 or psf2.
+    pub format: Version,
+
+    /// The version of psfu used. Currently it's always [`PSF2_VERSION`], and psf1 fonts also use 0.
     pub version: u32,
 
-    /// The size of the header in bytes. Pretty much always 32.
+    /// The size of the header in bytes. Pretty much always 32, or 4 for psf1.
     pub size: u32,
 
     /// Flags that specify a few things about the font. Currently there's only one.
@@ -60,18 +193,386 @@ pub struct Header {
     /// The number of glyphs.
     pub length: u32,
 
-    /// The size in bytes of each glyph.
+    /// The size in bytes (NOT pixels) of each glyph.
+    /// Each row is padded to a whole byte, so this is `glyph_height` times the bytes in each row,
+    /// which only equals `glyph_height` for fonts that are 8 pixels wide or less.
     pub glyph_size: u32,
 
-    /// The height of each glyph. In this library it always equals `glyph_size`.
+    /// The height of each glyph in pixels.
     pub glyph_height: u32,
 
-    /// The width of the glyphs.
+    /// The width of each glyph in pixels. For psf1 this is always 8.
     pub glyph_width: u32,
 }
 
+impl Header {
+    /// Parses just the header of a font, which is enough to know how big the rest of it is.
+    ///
+    /// This can be either psf1 or psf2. psf1 headers only use the first 4 bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The first [`HEADER_SIZE`] bytes of the font.
+    ///
+    /// # Errors
+    ///
+    /// * [`LoadError::BadMagic`] if the magic doesn't match.
+    /// * [`LoadError::UnsupportedVersion`] if the psf2 version isn't [`PSF2_VERSION`].
+    /// * [`LoadError::InvalidHeaderSize`] if the psf2 header says it's smaller than [`HEADER_SIZE`].
+    pub const fn parse(raw: &[u8; HEADER_SIZE]) -> Result<Self, LoadError> {
+        Self::parse_any(raw)
+    }
+
+    /// Checks whether the magic is [`PSF2_MAGIC`].
+    #[must_use]
+    pub const fn is_psf2(&self) -> bool {
+        matches!(self.magic, PSF2_MAGIC)
+    }
+
+    /// Checks whether the magic is [`PSF1_MAGIC`], with the rest of it left as 0.
+    #[must_use]
+    pub const fn is_psf1(&self) -> bool {
+        matches!(self.magic, [a, b, 0, 0] if a == PSF1_MAGIC[0] && b == PSF1_MAGIC[1])
+    }
+
+    /// Gets the amount of bytes taken up by the glyphs, which come right after the header.
+    ///
+    /// On 32 bit targets a malicious header can describe more than fits in a `usize`,
+    /// in which case this is `usize::MAX`. Fonts like that can't be loaded anyway.
+    #[must_use]
+    pub const fn glyph_data_len(&self) -> usize {
+        (self.glyph_size as usize).saturating_mul(self.length as usize)
+    }
+
+    /// Gets how many bytes the header and the glyphs take up together,
+    /// or `None` if that doesn't fit in a `usize`.
+    const fn checked_glyphs_end(&self) -> Option<usize> {
+        match (self.glyph_size as usize).checked_mul(self.length as usize) {
+            Some(len) => len.checked_add(self.size as usize),
+            None => None,
+        }
+    }
+
+    /// Checks that the glyphs aren't zero bytes, aren't too big to display,
+    /// and that their size matches their width and height.
+    const fn has_valid_dimensions(&self) -> bool {
+        self.glyph_size != 0
+            && self.glyph_width <= MAX_GLYPH_DIMENSION
+            && self.glyph_height <= MAX_GLYPH_DIMENSION
+            && matches!(
+                glyph_size_for(self.glyph_width, self.glyph_height),
+                Some(size) if size == self.glyph_size
+            )
+    }
+
+    /// Parses a header, working out whether it's psf1 or psf2 from the magic.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the font file itself.
+    const fn parse_any(raw: &[u8]) -> Result<Self, LoadError> {
+        match raw {
+            [a, b, ..] if *a == PSF1_MAGIC[0] && *b == PSF1_MAGIC[1] => Self::parse_psf1(raw),
+            _ => Self::parse_psf2(raw),
+        }
+    }
+
+    /// Parses a psf2 header.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the font file itself.
+    const fn parse_psf2(raw: &[u8]) -> Result<Self, LoadError> {
+        let Some(raw) = raw.first_chunk::<HEADER_SIZE>() else {
+            return Err(LoadError::TooShort);
+        };
+
+        let header = Self {
+            magic: field(raw, 0x0),
+            format: Version::Psf2,
+            version: as_u32_le(field(raw, 0x4)),
+            size: as_u32_le(field(raw, 0x8)),
+            flags: Flags::parse(field(raw, 0xc)),
+            length: as_u32_le(field(raw, 0x10)),
+            glyph_size: as_u32_le(field(raw, 0x14)),
+            glyph_height: as_u32_le(field(raw, 0x18)),
+            glyph_width: as_u32_le(field(raw, 0x1c)),
+        };
+
+        if !matches!(header.magic, PSF2_MAGIC) {
+            return Err(LoadError::BadMagic);
+        }
+
+        if header.version != PSF2_VERSION {
+            return Err(LoadError::UnsupportedVersion(header.version));
+        }
+
+        if (header.size as usize) < HEADER_SIZE {
+            return Err(LoadError::InvalidHeaderSize(header.size));
+        }
+
+        Ok(header)
+    }
+
+    /// Parses a psf1 header.
+    ///
+    /// psf1 glyphs are always 8 pixels wide, so the height
+    /// is the same as the size of a glyph in bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the font file itself.
+    const fn parse_psf1(raw: &[u8]) -> Result<Self, LoadError> {
+        if raw.len() < PSF1_HEADER_SIZE {
+            return Err(LoadError::TooShort);
+        }
+
+        let mode = raw[0x2];
+        let height = raw[0x3] as u32;
+
+        Ok(Self {
+            magic: [raw[0x0], raw[0x1], 0, 0],
+            format: Version::Psf1,
+            version: 0,
+            size: PSF1_HEADER_SIZE as u32,
+            flags: Flags::parse_psf1(mode),
+            length: if mode & 0x01 == 0 { 256 } else { 512 },
+            glyph_size: height,
+            glyph_height: height,
+            glyph_width: 8,
+        })
+    }
+}
+
+/// A rectangle, used for things like the area of a glyph that actually has set bits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bounds {
+    /// The x of the top left corner.
+    pub x: u16,
+
+    /// The y of the top left corner.
+    pub y: u16,
+
+    /// The width.
+    pub w: u16,
+
+    /// The height.
+    pub h: u16,
+}
+
+impl Bounds {
+    /// Checks whether a point is inside of the rectangle.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The x of the point.
+    /// * `y` - The y of the point.
+    #[must_use]
+    pub const fn contains(&self, x: u16, y: u16) -> bool {
+        x >= self.x && y >= self.y && x - self.x < self.w && y - self.y < self.h
+    }
+}
+
+/// A way to mirror a glyph when displaying it, see [`Font::display_glyph_transformed`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Transform {
+    /// Leaves the glyph as it is.
+    #[default]
+    None,
+
+    /// Mirrors the glyph from left to right.
+    FlipH,
+
+    /// Mirrors the glyph from top to bottom.
+    FlipV,
+
+    /// Turns the glyph upside down, which is the same as both flips at once.
+    Rotate180,
+}
+
+/// Settings for displaying a glyph with [`Font::display_glyph_with`],
+/// so they can be set up once and used for every glyph.
+///
+/// # Example
+///
+/// ```rust
+/// use psf_rs::{Font, RenderOptions};
+///
+/// let font = Font::load(include_bytes!("../test.psfu"));
+/// let options = RenderOptions::new().scale(2).flip_x(true).fallback('■');
+///
+/// font.display_glyph_with('A', &options, |bit, x, y| {
+///     // Stuff
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// How many times bigger the glyph should be.
+    scale: u8,
+
+    /// Whether to mirror the glyph from left to right.
+    flip_x: bool,
+
+    /// Whether to mirror the glyph from top to bottom.
+    flip_y: bool,
+
+    /// The character to display instead of ones that aren't in the font,
+    /// or `None` to use [`Font::fallback`].
+    fallback: Option<char>,
+
+    /// Which end of each byte the leftmost pixel is in.
+    bit_order: BitOrder,
+}
+
+impl RenderOptions {
+    /// Makes options that display glyphs the same way as [`Font::display_glyph`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            scale: 1,
+            flip_x: false,
+            flip_y: false,
+            fallback: None,
+            bit_order: BitOrder::MsbFirst,
+        }
+    }
+
+    /// Scales glyphs up by a whole number, the same as [`Font::display_glyph_scaled`].
+    /// A scale of 0 is treated as 1.
+    #[must_use]
+    pub const fn scale(mut self, scale: u8) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Mirrors glyphs from left to right, which happens before they're scaled.
+    #[must_use]
+    pub const fn flip_x(mut self, flip_x: bool) -> Self {
+        self.flip_x = flip_x;
+        self
+    }
+
+    /// Mirrors glyphs from top to bottom, which happens before they're scaled.
+    #[must_use]
+    pub const fn flip_y(mut self, flip_y: bool) -> Self {
+        self.flip_y = flip_y;
+        self
+    }
+
+    /// Displays a different character than [`Font::fallback`] for ones that aren't in the font.
+    #[must_use]
+    pub const fn fallback(mut self, fallback: char) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Reads the pixels out of each byte in a different order,
+    /// for bitmaps that don't follow the psf spec.
+    #[must_use]
+    pub const fn bit_order(mut self, bit_order: BitOrder) -> Self {
+        self.bit_order = bit_order;
+        self
+    }
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Which end of each byte the leftmost pixel of a row is in, see [`RenderOptions::bit_order`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BitOrder {
+    /// The leftmost pixel is the most significant bit, which is what psf always uses.
+    #[default]
+    MsbFirst,
+
+    /// The leftmost pixel is the least significant bit,
+    /// like in some other bitmap formats that were put into a psf file as they were.
+    LsbFirst,
+}
+
+/// Settings for loading a font with [`Font::try_load_with`].
+///
+/// # Example
+///
+/// ```rust
+/// use psf_rs::{Font, LoadOptions};
+///
+/// let options = LoadOptions::new().strict(true);
+/// let font = Font::try_load_with(include_bytes!("../test.psfu"), &options).unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadOptions {
+    /// Whether to refuse fonts with anything after the end of them.
+    strict: bool,
+}
+
+impl LoadOptions {
+    /// Makes options that load fonts the same way as [`Font::try_load`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { strict: false }
+    }
+
+    /// Refuses fonts that have extra bytes after their unicode table,
+    /// which usually means the file was corrupted or accidentally concatenated.
+    ///
+    /// Normally those bytes are ignored, which is what [`Font::load_many`] relies on.
+    #[must_use]
+    pub const fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+}
+
+/// A quarter turn to rotate a glyph by, see [`Font::display_glyph_rotated`].
+///
+/// Turning a glyph upside down doesn't swap its width and height, so that's [`Transform::Rotate180`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rotation {
+    /// Turns the glyph 90 degrees clockwise, so the left column becomes the top row.
+    R90,
+
+    /// Turns the glyph 270 degrees clockwise, so the left column becomes the bottom row.
+    R270,
+}
+
+/// How a character is represented in a font, see [`Font::lookup_kind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lookup {
+    /// The character has a glyph of its own, at the included index.
+    Single(usize),
+
+    /// The character only shows up as part of a sequence, like a combining accent.
+    SequenceOnly,
+
+    /// The character isn't in the font at all.
+    Absent,
+}
+
+/// What a character resolved to when it was displayed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GlyphResult {
+    /// The character is in the font.
+    Found,
+
+    /// The character isn't in the font, so the fallback was displayed instead.
+    Fallback,
+
+    /// Neither the character or the fallback are in the font, so the first glyph was displayed.
+    Missing,
+}
+
 /// The structure for the font.
 ///
+/// The parsed unicode table is kept inside the font, so it takes up room for `N` characters
+/// even if the table is smaller. With `N` set to 0 the table is never parsed,
+/// and characters are looked up in the raw table borrowed from the font file instead.
+/// That keeps the font small and cheap to move, at the cost of slower lookups.
+/// Everything else that's big, like an [`IndexCache`], is borrowed rather than stored in the font.
+///
 /// # Example
 ///
 /// ```rust
@@ -83,176 +584,2054 @@ pub struct Header {
 ///    // Stuff
 /// });
 /// ```
-#[derive(Debug)]
-pub struct Font<'a> {
+#[derive(Clone, Debug)]
+pub struct Font<'a, const N: usize = UNICODE_CAPACITY> {
     /// The font header for this font.
     pub header: Header,
 
-    /// The data NOT including the header.
-    data: &'a [u8],
+    /// The glyphs, NOT including the header or the unicode table.
+    data: Data<'a>,
+
+    /// The raw unicode table, which is empty if there isn't one.
+    table: Data<'a>,
 
     /// The parsed unicode table.
-    unicode: Option<HashMap>,
+    unicode: Option<UnicodeTable<N>>,
+
+    /// The character displayed in place of ones that aren't in the font.
+    fallback: char,
+
+    /// Glyph indices worked out ahead of time, see [`Font::set_index_cache`].
+    cache: Option<&'a IndexCache>,
 }
 
-impl<'a> Font<'a> {
-    /// Converts the unicode table in a font to a hashmap.
-    ///
-    /// # Arguments
-    ///
-    /// * `table` - A byte slice of the actual unicode table.
-    fn parse_unicode_table(table: &[u8]) -> HashMap {
-        let mut result: HashMap = HashMap::new();
+/// The glyph indices of a range of characters, worked out ahead of time
+/// so [`Font::glyph_index`] doesn't need to search the unicode table for them.
+///
+/// A cache takes up `4 * INDEX_CACHE_SIZE` bytes, whatever range it covers,
+/// and looking up a cached character is just indexing into an array instead of a search.
+/// It holds at most [`INDEX_CACHE_SIZE`] characters, starting from the start of the range,
+/// and anything past that is left out and still searched for like usual.
+///
+/// This is kept separately from the font, so fonts that don't use one don't pay for it,
+/// which is why it's made with [`IndexCache::new`] and then lent to a font with
+/// [`Font::set_index_cache`], rather than being built inside the font.
+///
+/// # Example
+///
+/// ```rust
+/// use psf_rs::{Font, IndexCache};
+///
+/// let mut font = Font::load(include_bytes!("../test.psfu"));
+/// let cache = IndexCache::new(&font, 0x80..0x180);
+/// font.set_index_cache(&cache);
+///
+/// assert!(font.glyph_index('é' as u32).is_some());
+/// ```
+#[derive(Clone, Debug)]
+pub struct IndexCache {
+    /// The first character in the cache.
+    start: u32,
 
-        for (i, entry) in table.split(|x| x == &0xff).enumerate() {
-            let mut iter = entry.iter().enumerate();
-            while let Some((j, byte)) = iter.next() {
-                let utf8_len = match byte >> 4usize {
-                    0xc | 0xd => 2,
-                    0xe => 3,
-                    0xf => 4,
-                    _ => 1,
-                };
+    /// The amount of characters in the cache.
+    len: u32,
 
-                let mut key = [0; 4];
+    /// The glyph index of each character, or [`IndexCache::MISSING`] if it isn't in the font.
+    indices: [u32; INDEX_CACHE_SIZE],
+}
 
-                key[..utf8_len].copy_from_slice(&entry[j..j + utf8_len]);
-                result.insert(key, i).unwrap();
+impl IndexCache {
+    /// Marks characters that aren't in the unicode table.
+    const MISSING: u32 = u32::MAX;
 
-                for _ in 0..utf8_len - 1 {
-                    if iter.next().is_none() {
-                        break;
-                    }
-                }
+    /// Looks up a range of characters in a font ahead of time.
+    ///
+    /// ASCII characters are always fast, so the most useful range is usually something like Latin-1.
+    ///
+    /// # Arguments
+    ///
+    /// * `font` - The font to look the characters up in, which is the only one the cache should be used with.
+    /// * `chars` - The characters to cache, as Unicode Scalar Values.
+    ///   Only the first [`INDEX_CACHE_SIZE`] are kept, and the rest aren't cached at all.
+    #[must_use]
+    pub fn new<const N: usize>(font: &Font<'_, N>, chars: core::ops::Range<u32>) -> Self {
+        let len = chars.len().min(INDEX_CACHE_SIZE);
+        let mut indices = [Self::MISSING; INDEX_CACHE_SIZE];
+
+        for (index, char) in indices[..len].iter_mut().zip(chars.clone()) {
+            if let Some(found) = font.glyph_index(char) {
+                *index = found as u32;
             }
         }
 
-        result
+        Self {
+            start: chars.start,
+            len: len as u32,
+            indices,
+        }
     }
 
-    /// Gets the glyph index of a character by using the fonts own unicode table.
-    /// This index is where the glyph in the font itself.
+    /// Gets the cached index of a character, which might be [`IndexCache::MISSING`].
+    ///
+    /// Returns `None` if the character isn't cached at all.
+    fn get(&self, char: u32) -> Option<u32> {
+        let offset = char
+            .checked_sub(self.start)
+            .filter(|offset| *offset < self.len)?;
+
+        Some(self.indices[offset as usize])
+    }
+}
+
+impl<'a, const N: usize> Font<'a, N> {
+    /// Converts the unicode table in a font to a sorted list of codepoints.
+    ///
+    /// Sequences of characters (which come after a `0xfe` in an entry) are skipped,
+    /// since they can't be looked up as a single character.
+    /// See [`Font::sequence_index`] for those.
     ///
     /// # Arguments
     ///
-    /// * `char` - The Unicode Scalar Value of the character you want the index of. (Just cast a char to u32)
+    /// * `table` - A byte slice of the actual unicode table.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// * If the unicode table flag is set to true, but the table hasn't yet been defined.
-    fn glyph_index(&self, char: u32) -> Option<usize> {
-        // Should work for basic ASCII.
-        if !self.header.flags.unicode || char < 128 {
-            return Some(char as usize);
-        }
+    /// * [`LoadError::UnicodeTableFull`] if there are more than `N` characters.
+    fn parse_unicode_table(table: &[u8]) -> Result<UnicodeTable<N>, LoadError> {
+        let mut result = UnicodeTable::new();
 
-        let mut utf8 = [0; 4];
-        char::from_u32(char).unwrap().encode_utf8(&mut utf8);
+        for (i, entry) in table.split(|x| x == &0xff).enumerate() {
+            let mut entry = entry.split(|x| x == &0xfe).next().unwrap_or_default();
 
-        self.unicode
-            .as_ref()
-            .expect("unicode table doesn't exist, but header states otherwise")
-            .get(&utf8)
-            .copied()
+            while let Some((len, codepoint)) = decode_utf8(entry) {
+                if let Some(codepoint) = codepoint {
+                    insert_entry(&mut result, codepoint, i)?;
+                }
+
+                entry = &entry[len..];
+            }
+        }
+
+        Ok(result)
     }
 
-    /// Displays a glyph.
-    /// This will NOT trim the glyph, so you will still get the vertical padding.
+    /// Converts the unicode table in a psf1 font to a sorted list of codepoints.
+    ///
+    /// psf1 stores each codepoint as a little endian u16, with `0xffff` ending
+    /// the entry for a glyph and `0xfffe` starting a sequence, which gets skipped.
     ///
     /// # Arguments
     ///
-    /// * `char` - Pretty self explanitory. A character or integer, that must represent a glyph on the ASCII table.
-    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    /// * `table` - A byte slice of the actual unicode table.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// * If the character can't be properly converted into a u32.
-    /// * If the character can't be described with 2 bytes or less in UTF-8.
-    pub fn display_glyph<T: TryInto<u32>>(&self, char: T, mut action: impl FnMut(u8, u8, u8)) {
-        let Ok(char) = TryInto::<u32>::try_into(char) else {
-            panic!("invalid character index")
-        };
-
-        let char = self.glyph_index(char).map_or('?' as usize, |value| value) as u32;
+    /// * [`LoadError::UnicodeTableFull`] if there are more than `N` characters.
+    fn parse_psf1_unicode_table(table: &[u8]) -> Result<UnicodeTable<N>, LoadError> {
+        let mut result = UnicodeTable::new();
+        let mut glyph = 0;
+        let mut in_sequence = false;
 
-        let from = self.header.glyph_size * (char);
-        let to = self.header.glyph_size * (char + 1);
-
-        let data = &self.data[from as usize..to as usize];
-        let bytes_in_row = ((self.header.glyph_width as usize + 7) & !7) / 8;
-
-        for (i, row) in data.chunks(bytes_in_row).enumerate() {
-            'row: for (j, byte) in row.iter().enumerate() {
-                for k in 0..8 {
-                    let x = (j as u8 * 8) + k;
-
-                    if x as u32 > self.header.glyph_width {
-                        break 'row;
+        for value in table.chunks_exact(2) {
+            match u16::from_le_bytes([value[0], value[1]]) {
+                0xffff => {
+                    glyph += 1;
+                    in_sequence = false;
+                }
+                0xfffe => in_sequence = true,
+                _ if in_sequence => (),
+                codepoint => {
+                    if char::from_u32(codepoint as u32).is_some() {
+                        insert_entry(&mut result, codepoint as u32, glyph)?;
                     }
-
-                    // Bit is a u8 that is always either a 0 or a 1.
-                    // "But why not use a boolean?" I hear you ask.
-                    // Every variable in rust is always at least one byte in size,
-                    // So it doesn't do much for saving memory.
-                    let bit = (byte >> (7 - k)) & 1;
-
-                    action(bit, x, i as u8);
                 }
             }
         }
+
+        Ok(result)
     }
 
-    /// Loads a font.
+    /// Gets the glyph index of a character by using the fonts own unicode table.
+    /// This index is where the glyph is in the font itself, and can be used with [`Font::glyph_data`].
     ///
-    /// # Arguments
+    /// ASCII characters, and every character in fonts without a unicode table,
+    /// are always their own index, even if the font doesn't have that many glyphs.
+    /// Anything else is looked up in the unicode table.
     ///
-    /// * `raw` - The raw bytes for the font file itself.
+    /// # Arguments
     ///
-    /// # Panics
+    /// * `char` - The Unicode Scalar Value of the character you want the index of. (Just cast a char to u32)
     ///
-    /// * If the file header is incomplete/corrupted in pretty much any way.
-    /// * If the magic doesn't match.
+    /// Returns `None` if the character isn't in the unicode table, or isn't a valid Unicode Scalar Value.
+    /// This includes when the header says there's a unicode table, but the font doesn't have one.
+    /// Invalid UTF-8 in the table never matches anything, see [`Font::try_load`].
     #[must_use]
-    pub fn load(raw: &'a [u8]) -> Self {
-        let header_size = as_u32_le(&raw[0x8..0xc]);
-        let header = Header {
-            magic: [raw[0x0], raw[0x1], raw[0x2], raw[0x3]],
-            version: as_u32_le(&raw[0x4..0x8]),
-            size: header_size,
-            flags: Flags::parse(&raw[0xc..0x10]),
-            length: as_u32_le(&raw[0x10..0x14]),
-            glyph_size: as_u32_le(&raw[0x14..0x18]),
-            glyph_height: as_u32_le(&raw[0x18..0x1c]),
-            glyph_width: as_u32_le(&raw[0x1c..0x20]),
-        };
-        let data = &raw[header_size as usize..];
+    pub fn glyph_index(&self, char: u32) -> Option<usize> {
+        // Should work for basic ASCII.
+        if !self.header.flags.unicode || char < 128 {
+            return Some(char as usize);
+        }
+
+        if let Some(index) = self.cache.as_ref().and_then(|cache| cache.get(char)) {
+            return (index != IndexCache::MISSING).then_some(index as usize);
+        }
+
+        self.table_index(char)
+    }
+
+    /// Looks a character up in the unicode table, without checking the cache.
+    fn table_index(&self, char: u32) -> Option<usize> {
+        let Some(table) = self.unicode.as_ref() else {
+            // The table hasn't been parsed, so the raw one has to be searched instead.
+            return self.glyph_indices(char::from_u32(char)?).last();
+        };
+
+        let i = table
+            .binary_search_by_key(&char, |(codepoint, _)| *codepoint)
+            .ok()?;
+
+        Some(table[i].1)
+    }
+
+    /// Makes [`Font::glyph_index`] (and everything that displays glyphs) check a cache first,
+    /// so the characters in it don't need to be searched for in the unicode table.
+    ///
+    /// The cache has to be made from this font with [`IndexCache::new`],
+    /// and setting another one replaces the old one.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The glyph indices to use.
+    pub const fn set_index_cache(&mut self, cache: &'a IndexCache) {
+        self.cache = Some(cache);
+    }
+
+    /// Gets the glyph index of a sequence of characters, like a letter followed by a combining accent.
+    ///
+    /// Sequences are kept separately from single characters in the unicode table,
+    /// so they can't be found with [`Font::glyph_index`].
+    /// They also aren't parsed when loading, so this searches the whole table every time.
+    /// Sequences longer than [`MAX_SEQUENCE`] characters can't be found.
+    ///
+    /// # Arguments
+    ///
+    /// * `sequence` - The characters that make up the sequence, in order.
+    ///
+    /// Returns `None` if the sequence isn't in the unicode table.
+    #[must_use]
+    pub fn sequence_index(&self, sequence: &[char]) -> Option<usize> {
+        let mut found = None;
+
+        self.for_each_sequence(|glyph, candidate| {
+            if found.is_none() && candidate == sequence {
+                found = Some(glyph);
+            }
+        });
+
+        found
+    }
+
+    /// Calls `action` for every sequence in the unicode table, along with the glyph it maps to.
+    ///
+    /// Sequences longer than [`MAX_SEQUENCE`] characters, or with invalid characters, are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - A closure that takes in the glyph index and the characters of the sequence.
+    fn for_each_sequence(&self, mut action: impl FnMut(usize, &[char])) {
+        if !self.header.flags.unicode {
+            return;
+        }
+
+        let table = &self.table;
+        let mut sequence = heapless::Vec::<char, MAX_SEQUENCE>::new();
+
+        match self.header.format {
+            Version::Psf1 => {
+                let mut glyph = 0;
+                let mut in_sequence = false;
+                let mut valid = true;
+
+                for value in table.chunks_exact(2) {
+                    let value = u16::from_le_bytes([value[0], value[1]]);
+
+                    if value == 0xfffe || value == 0xffff {
+                        if in_sequence && valid {
+                            action(glyph, &sequence);
+                        }
+
+                        sequence.clear();
+                        valid = true;
+                        in_sequence = value == 0xfffe;
+                        glyph += (value == 0xffff) as usize;
+                    } else if in_sequence {
+                        valid &= char::from_u32(value as u32)
+                            .is_some_and(|char| sequence.push(char).is_ok());
+                    }
+                }
+            }
+            Version::Psf2 => {
+                for (glyph, entry) in table.split(|x| x == &0xff).enumerate() {
+                    for candidate in entry.split(|x| x == &0xfe).skip(1) {
+                        let Ok(candidate) = core::str::from_utf8(candidate) else {
+                            continue;
+                        };
+
+                        sequence.clear();
+                        if candidate.chars().all(|char| sequence.push(char).is_ok()) {
+                            action(glyph, &sequence);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Gets an iterator over every character in the unicode table, along with the glyph it maps to.
+    ///
+    /// This is empty if the font doesn't have a unicode table.
+    /// Sequences aren't included, see [`Font::sequence_index`] for those.
+    ///
+    /// They're sorted by character, unless the table hasn't been parsed (see [`Font::try_load_lazy`]),
+    /// in which case they're in the order of the raw table and a character can come up more than once.
+    pub fn unicode_entries(&self) -> impl Iterator<Item = (char, usize)> + '_ {
+        let parsed = self.unicode.iter().flatten().copied();
+        let raw = self.unicode.is_none().then(|| self.raw_entries());
+
+        parsed
+            .chain(raw.into_iter().flatten())
+            .filter_map(|(codepoint, index)| Some((char::from_u32(codepoint)?, index)))
+    }
+
+    /// Gets an iterator over every character that resolves to a glyph with [`Font::glyph_index`].
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the glyph in the font.
+    pub fn chars_for_glyph(&self, index: usize) -> impl Iterator<Item = char> + '_ {
+        // These are the same characters that `glyph_index` passes straight through.
+        let passthrough = (!self.header.flags.unicode || index < 128)
+            .then(|| char::from_u32(index as u32))
+            .flatten();
+
+        passthrough.into_iter().chain(
+            self.unicode_entries()
+                .filter(move |(char, glyph)| *glyph == index && !char.is_ascii())
+                .map(|(char, _)| char),
+        )
+    }
+
+    /// Gets the main character for a glyph, which is the one with the smallest codepoint,
+    /// like for labelling glyphs in a font viewer.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the glyph in the font.
+    ///
+    /// Returns `None` if no character resolves to the glyph, see [`Font::chars_for_glyph`].
+    #[must_use]
+    pub fn primary_char_for(&self, index: usize) -> Option<char> {
+        self.chars_for_glyph(index).min()
+    }
+
+    /// Gets an iterator over every glyph the unicode table maps a character to, in order.
+    ///
+    /// Fonts can accidentally map the same character to more than one glyph,
+    /// in which case [`Font::glyph_index`] only gives the last one.
+    /// This searches the whole table every time, so it's mostly useful for checking fonts.
+    ///
+    /// Fonts without a unicode table just give the character itself, if there's a glyph for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - The character to find the glyphs of.
+    pub fn glyph_indices(&self, char: char) -> impl Iterator<Item = usize> + '_ {
+        let passthrough = (!self.header.flags.unicode && self.is_valid_index(char as usize))
+            .then_some(char as usize);
+
+        passthrough.into_iter().chain(
+            self.raw_entries()
+                .filter(move |(codepoint, _)| *codepoint == char as u32)
+                .map(|(_, glyph)| glyph),
+        )
+    }
+
+    /// Gets an iterator over every character in the raw unicode table, along with the glyph it maps to.
+    ///
+    /// They're in the order they're in the table, which isn't sorted and can have repeats.
+    /// Sequences are skipped.
+    fn raw_entries(&self) -> impl Iterator<Item = (u32, usize)> + '_ {
+        let unicode = self.header.flags.unicode;
+
+        let (mut glyph, mut in_sequence) = (0, false);
+        let psf1 = (unicode && self.header.format == Version::Psf1).then(|| {
+            self.table.chunks_exact(2).filter_map(move |value| {
+                match u16::from_le_bytes([value[0], value[1]]) {
+                    0xffff => {
+                        glyph += 1;
+                        in_sequence = false;
+                        None
+                    }
+                    0xfffe => {
+                        in_sequence = true;
+                        None
+                    }
+                    codepoint => (!in_sequence).then_some((codepoint as u32, glyph)),
+                }
+            })
+        });
+
+        let psf2 = (unicode && self.header.format == Version::Psf2).then(|| {
+            self.table
+                .split(|x| x == &0xff)
+                .enumerate()
+                .flat_map(|(glyph, entry)| {
+                    let mut entry = entry.split(|x| x == &0xfe).next().unwrap_or_default();
+
+                    core::iter::from_fn(move || loop {
+                        let (len, codepoint) = decode_utf8(entry)?;
+                        entry = &entry[len..];
+
+                        if let Some(codepoint) = codepoint {
+                            return Some((codepoint, glyph));
+                        }
+                    })
+                })
+        });
+
+        psf1.into_iter().flatten().chain(psf2.into_iter().flatten())
+    }
+
+    /// Checks whether the font has a glyph for a character, without using the fallback.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - The character to check.
+    #[must_use]
+    pub fn has_glyph(&self, char: char) -> bool {
+        self.glyph_index(char as u32)
+            .is_some_and(|index| index < self.header.length as usize)
+    }
+
+    /// Works out whether a character has a glyph of its own, or can only be displayed
+    /// as part of a sequence, like how a combining accent might only be in the font
+    /// already combined with a letter.
+    ///
+    /// This searches the sequences in the unicode table every time
+    /// the character doesn't have its own glyph.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - The character to look up.
+    #[must_use]
+    pub fn lookup_kind(&self, char: char) -> Lookup {
+        if let Some(index) = self
+            .glyph_index(char as u32)
+            .filter(|index| self.is_valid_index(*index))
+        {
+            return Lookup::Single(index);
+        }
+
+        let mut in_sequence = false;
+        self.for_each_sequence(|_, sequence| in_sequence |= sequence.contains(&char));
+
+        if in_sequence {
+            Lookup::SequenceOnly
+        } else {
+            Lookup::Absent
+        }
+    }
+
+    /// Counts how many characters in a string the font has a glyph for, see [`Font::has_glyph`].
+    ///
+    /// This is useful for picking whichever font covers a piece of text best.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The string to check.
+    #[must_use]
+    pub fn coverage(&self, text: &str) -> usize {
+        text.chars().filter(|char| self.has_glyph(*char)).count()
+    }
+
+    /// Sets the character that gets displayed in place of characters that aren't in the font.
+    /// By default, this is `'?'`.
+    ///
+    /// If the fallback isn't in the font either, the first glyph in the font is used instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `fallback` - The new fallback character.
+    pub const fn set_fallback(&mut self, fallback: char) {
+        self.fallback = fallback;
+    }
+
+    /// Gets the character that gets displayed in place of characters that aren't in the font.
+    #[must_use]
+    pub const fn fallback(&self) -> char {
+        self.fallback
+    }
+
+    /// Gets the width of each glyph in pixels.
+    #[must_use]
+    pub const fn width(&self) -> u32 {
+        self.header.glyph_width
+    }
+
+    /// Gets the height of each glyph in pixels.
+    #[must_use]
+    pub const fn height(&self) -> u32 {
+        self.header.glyph_height
+    }
+
+    /// Gets the amount of bytes in each row of a glyph, since rows are padded to a whole byte.
+    #[must_use]
+    pub const fn bytes_per_row(&self) -> u32 {
+        self.header.glyph_width.div_ceil(8)
+    }
+
+    /// Gets the amount of glyphs in the font.
+    #[must_use]
+    pub const fn glyph_count(&self) -> u32 {
+        self.header.length
+    }
+
+    /// Gets how many bytes the font takes up in the file it was loaded from,
+    /// which is the header, the glyphs, and the unicode table.
+    ///
+    /// Anything after the font, like another font in [`Font::load_many`], isn't included.
+    #[must_use]
+    // Slices can be measured in a const fn, but `Cow`s can't.
+    #[cfg_attr(not(feature = "alloc"), allow(clippy::missing_const_for_fn))]
+    pub fn byte_len(&self) -> usize {
+        self.header.size as usize + self.data.len() + self.table.len()
+    }
+
+    /// Checks whether there's a glyph at an index, which is anything below [`Font::glyph_count`].
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the glyph in the font, NOT a character.
+    #[must_use]
+    pub const fn is_valid_index(&self, index: usize) -> bool {
+        index < self.header.length as usize
+    }
+
+    /// Checks the font for things that are allowed when loading, but are probably mistakes.
+    ///
+    /// Returns every problem that was found, which is empty for a good font.
+    /// A glyph size mismatch is refused when loading, so that warning only comes up
+    /// if [`Font::header`] was changed afterwards.
+    pub fn validate(&self) -> impl Iterator<Item = ValidationWarning> + '_ {
+        let expected = glyph_size_for(self.header.glyph_width, self.header.glyph_height);
+        let size = (expected != Some(self.header.glyph_size)).then_some(
+            ValidationWarning::GlyphSizeMismatch {
+                expected: expected.unwrap_or(u32::MAX),
+                actual: self.header.glyph_size,
+            },
+        );
+
+        let entries = self
+            .header
+            .flags
+            .unicode
+            .then(|| self.unicode_table_entries())
+            .filter(|entries| *entries != self.header.length)
+            .map(|actual| ValidationWarning::UnicodeEntriesMismatch {
+                expected: self.header.length,
+                actual,
+            });
+
+        // Lazy fonts and fonts with no capacity don't have a parsed table, so the raw one is checked.
+        let empty = self
+            .unicode
+            .as_ref()
+            .map_or_else(
+                || self.header.flags.unicode && self.raw_entries().next().is_none(),
+                heapless::Vec::is_empty,
+            )
+            .then_some(ValidationWarning::EmptyUnicodeTable);
+
+        size.into_iter().chain(entries).chain(empty)
+    }
+
+    /// Counts the entries in the unicode table by their terminators.
+    fn unicode_table_entries(&self) -> u32 {
+        let terminator = self.header.format.entry_terminator();
+
+        self.table
+            .chunks_exact(terminator.len())
+            .filter(|chunk| chunk == &terminator)
+            .count() as u32
+    }
+
+    /// Gets the raw bitmap of a glyph, with each row padded to a whole byte.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the glyph in the font, NOT a character.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    #[must_use]
+    pub fn glyph_data(&self, index: u32) -> Option<&[u8]> {
+        if !self.is_valid_index(index as usize) {
+            return None;
+        }
+
+        let size = self.header.glyph_size as usize;
+        let from = size.checked_mul(index as usize)?;
+
+        self.data.get(from..from.checked_add(size)?)
+    }
+
+    /// Gets an iterator over every glyph in the font, paired with its index.
+    ///
+    /// This includes glyphs that no character maps to, and each bitmap
+    /// is the same as what [`Font::glyph_data`] gives.
+    pub fn glyphs(&self) -> impl Iterator<Item = (usize, &[u8])> + '_ {
+        (0..self.header.length)
+            .filter_map(move |index| Some((index as usize, self.glyph_data(index)?)))
+    }
+
+    /// Gets the raw bitmap of the glyph a character maps to, the same as [`Font::glyph_data`].
+    ///
+    /// Characters that share a glyph get the exact same slice.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - The character to get the glyph of.
+    ///
+    /// Returns `None` if the font doesn't have a glyph for the character.
+    /// Unlike [`Font::display_glyph`], the fallback isn't used.
+    #[must_use]
+    pub fn glyph_bytes(&self, char: char) -> Option<&[u8]> {
+        let index = u32::try_from(self.glyph_index(char as u32)?).ok()?;
+
+        self.glyph_data(index)
+    }
+
+    /// Gets one row of the raw bitmap of the glyph a character maps to,
+    /// for copying whole rows at a time.
+    ///
+    /// The last [`Font::row_padding_bits`] bits of the row are padding,
+    /// and aren't guaranteed to be 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - The character to get the glyph of.
+    /// * `row` - The row to get, with 0 being the top.
+    ///
+    /// Returns `None` if the font doesn't have a glyph for the character, or `row` is past the bottom.
+    /// Like [`Font::glyph_bytes`], the fallback isn't used.
+    #[must_use]
+    pub fn glyph_row_bytes(&self, char: char, row: u32) -> Option<&[u8]> {
+        if row >= self.header.glyph_height {
+            return None;
+        }
+
+        self.glyph_bytes(char)?
+            .chunks_exact(self.bytes_per_row() as usize)
+            .nth(row as usize)
+    }
+
+    /// Gets how many bits at the end of each row are padding, since rows are padded to a whole byte.
+    #[must_use]
+    pub const fn row_padding_bits(&self) -> u32 {
+        self.bytes_per_row() * 8 - self.header.glyph_width
+    }
+
+    /// Checks whether two glyphs have exactly the same bitmap.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The index of the first glyph.
+    /// * `b` - The index of the second glyph.
+    ///
+    /// Returns `false` if either index is out of bounds.
+    #[must_use]
+    pub fn glyph_eq(&self, a: u32, b: u32) -> bool {
+        match (self.glyph_data(a), self.glyph_data(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Gets an iterator over every pair of glyphs that have exactly the same bitmap.
+    ///
+    /// Each pair is only given once, with the smaller index first.
+    pub fn duplicate_glyphs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let length = self.header.length;
+
+        (0..length).flat_map(move |a| {
+            (a + 1..length)
+                .filter(move |b| self.glyph_eq(a, *b))
+                .map(move |b| (a as usize, b as usize))
+        })
+    }
+
+    /// Counts how many bits are set in a glyph, which says how heavy it looks.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    #[must_use]
+    pub fn glyph_weight<T: TryInto<u32>>(&self, char: T) -> u32 {
+        self.masked_bytes(self.resolve(char))
+            .map(u8::count_ones)
+            .sum()
+    }
+
+    /// Checks whether a glyph has no set bits at all, like a space.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    #[must_use]
+    pub fn is_blank<T: TryInto<u32>>(&self, char: T) -> bool {
+        self.masked_bytes(self.resolve(char)).all(|byte| byte == 0)
+    }
+
+    /// Displays a glyph.
+    /// This will NOT trim the glyph, so you will still get the vertical padding.
+    ///
+    /// If the character isn't in the font, the fallback (see [`Font::set_fallback`]) is displayed instead.
+    /// The returned [`GlyphResult`] says which of these happened.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - Pretty self explanitory. A character or integer, that must represent a glyph on the ASCII table.
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///
+    /// # Panics
+    ///
+    /// * If the character can't be properly converted into a u32, like a negative integer.
+    /// * If the glyph the character resolves to isn't in the font.
+    ///
+    /// Fonts without any glyphs, like [`Font::empty`], don't panic and just display nothing.
+    pub fn display_glyph<T: TryInto<u32>>(
+        &self,
+        char: T,
+        action: impl FnMut(u8, u8, u8),
+    ) -> GlyphResult {
+        let (data, result) = self.lookup(char);
+        self.for_each_bit(data, action);
+
+        result
+    }
+
+    /// Displays a glyph, returning an error instead of panicking.
+    ///
+    /// This is the same as [`Font::display_glyph`], for fonts that can't be trusted.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///
+    /// # Errors
+    ///
+    /// * [`RenderError::InvalidCharacter`] if `char` can't be converted into a u32.
+    /// * [`RenderError::GlyphDataTruncated`] if neither the character, the fallback,
+    ///   nor the first glyph have any glyph data.
+    pub fn try_display_glyph<T: TryInto<u32>>(
+        &self,
+        char: T,
+        action: impl FnMut(u8, u8, u8),
+    ) -> Result<GlyphResult, RenderError> {
+        let (data, result) = self.try_lookup(char, self.fallback)?;
+        self.for_each_bit(data, action);
+
+        Ok(result)
+    }
+
+    /// Displays the glyph at an index, without resolving a character first.
+    ///
+    /// This is for when the index is already known, like from an atlas or from
+    /// [`Font::glyph_index`], and the fallback should never be displayed instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the glyph in the font, NOT a character.
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///
+    /// Returns `false` without displaying anything if `index` is out of bounds.
+    pub fn display_glyph_by_index(&self, index: usize, action: impl FnMut(u8, u8, u8)) -> bool {
+        let Some(data) = u32::try_from(index)
+            .ok()
+            .and_then(|index| self.glyph_data(index))
+        else {
+            return false;
+        };
+
+        self.for_each_bit(data, action);
+        true
+    }
+
+    /// Displays only part of a glyph, like for redrawing just the part of a cell that changed.
+    ///
+    /// Rows and columns outside of `region` are skipped entirely,
+    /// and anything past the edge of the glyph is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `region` - The part of the glyph to display, relative to its top left corner.
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///   The coordinates are still relative to the glyph, not the region.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn display_glyph_region<T: TryInto<u32>>(
+        &self,
+        char: T,
+        region: Bounds,
+        mut action: impl FnMut(u8, u8, u8),
+    ) -> GlyphResult {
+        let (data, result) = self.lookup(char);
+        let bytes_in_row = self.bytes_per_row() as usize;
+
+        let right = (region.x as u32 + region.w as u32).min(self.header.glyph_width) as usize;
+        let bottom = (region.y as u32 + region.h as u32).min(self.header.glyph_height) as usize;
+
+        for (y, row) in data
+            .chunks(bytes_in_row)
+            .enumerate()
+            .take(bottom)
+            .skip(region.y as usize)
+        {
+            for x in region.x as usize..right {
+                action((row[x / 8] >> (7 - x % 8)) & 1, x as u8, y as u8);
+            }
+        }
+
+        result
+    }
+
+    /// Displays a glyph, mirroring where each bit ends up.
+    ///
+    /// Only the coordinates change, the bits themselves are the same as in [`Font::display_glyph`].
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `transform` - How the glyph should be mirrored.
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///
+    /// Returns which glyph was displayed, the same as [`Font::display_glyph`].
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn display_glyph_transformed<T: TryInto<u32>>(
+        &self,
+        char: T,
+        transform: Transform,
+        mut action: impl FnMut(u8, u8, u8),
+    ) -> GlyphResult {
+        let (data, result) = self.lookup(char);
+        let last_x = self.header.glyph_width.saturating_sub(1) as u8;
+        let last_y = self.header.glyph_height.saturating_sub(1) as u8;
+
+        self.for_each_bit(data, |bit, x, y| match transform {
+            Transform::None => action(bit, x, y),
+            Transform::FlipH => action(bit, last_x - x, y),
+            Transform::FlipV => action(bit, x, last_y - y),
+            Transform::Rotate180 => action(bit, last_x - x, last_y - y),
+        });
+
+        result
+    }
+
+    /// Displays a glyph turned on its side, like for a display that's mounted sideways.
+    ///
+    /// The glyph's width and height swap, so x goes up to the glyph height and y up to the glyph width.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `rotation` - Which way the glyph should be turned.
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///
+    /// Returns the width and height of the rotated glyph.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn display_glyph_rotated<T: TryInto<u32>>(
+        &self,
+        char: T,
+        rotation: Rotation,
+        mut action: impl FnMut(u8, u8, u8),
+    ) -> (u32, u32) {
+        let last_x = self.header.glyph_width.saturating_sub(1) as u8;
+        let last_y = self.header.glyph_height.saturating_sub(1) as u8;
+
+        self.for_each_bit(self.resolve(char), |bit, x, y| match rotation {
+            Rotation::R90 => action(bit, last_y - y, x),
+            Rotation::R270 => action(bit, y, last_x - x),
+        });
+
+        (self.header.glyph_height, self.header.glyph_width)
+    }
+
+    /// Displays a glyph with y going up from the bottom row instead of down from the top,
+    /// which is what coordinate systems like OpenGL's expect.
+    ///
+    /// This is the same as using [`Transform::FlipV`] with [`Font::display_glyph_transformed`].
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///
+    /// Returns which glyph was displayed, the same as [`Font::display_glyph`].
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn display_glyph_flipped_y<T: TryInto<u32>>(
+        &self,
+        char: T,
+        action: impl FnMut(u8, u8, u8),
+    ) -> GlyphResult {
+        self.display_glyph_transformed(char, Transform::FlipV, action)
+    }
+
+    /// Displays a glyph at a position, skipping any bits that land outside of a clipping rectangle.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `origin_x` - Where the left edge of the glyph goes.
+    /// * `origin_y` - Where the top edge of the glyph goes.
+    /// * `clip` - The area that bits are allowed to be in, in the same coordinates as the origin.
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1),
+    ///   and the x and y with the origin already added.
+    ///
+    /// Returns which glyph was displayed, the same as [`Font::display_glyph`].
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn display_glyph_clipped<T: TryInto<u32>>(
+        &self,
+        char: T,
+        origin_x: u16,
+        origin_y: u16,
+        clip: Bounds,
+        mut action: impl FnMut(u8, u16, u16),
+    ) -> GlyphResult {
+        let (data, result) = self.lookup(char);
+
+        self.for_each_bit(data, |bit, x, y| {
+            let (Some(x), Some(y)) = (
+                origin_x.checked_add(x as u16),
+                origin_y.checked_add(y as u16),
+            ) else {
+                return;
+            };
+
+            if clip.contains(x, y) {
+                action(bit, x, y);
+            }
+        });
+
+        result
+    }
+
+    /// Displays two glyphs side by side, as one cell that's twice as wide, like for wide characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `left` - A character or integer for the left half, the same as in [`Font::display_glyph`].
+    /// * `right` - A character or integer for the right half.
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///   The x of the right glyph starts at the width of a glyph.
+    ///
+    /// Returns what happened when looking up each glyph, left first.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn display_glyph_pair<L: TryInto<u32>, R: TryInto<u32>>(
+        &self,
+        left: L,
+        right: R,
+        mut action: impl FnMut(u8, u16, u16),
+    ) -> (GlyphResult, GlyphResult) {
+        let offset = self.header.glyph_width as u16;
+
+        (
+            self.display_glyph(left, |bit, x, y| action(bit, x as u16, y as u16)),
+            self.display_glyph(right, |bit, x, y| action(bit, x as u16 + offset, y as u16)),
+        )
+    }
+
+    /// Displays a glyph scaled up by a whole number, so each bit becomes a `scale` by `scale` block.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `scale` - How many times bigger the glyph should be. A scale of 0 is treated as 1.
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///
+    /// Returns which glyph was displayed, the same as [`Font::display_glyph`].
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn display_glyph_scaled<T: TryInto<u32>>(
+        &self,
+        char: T,
+        scale: u8,
+        action: impl FnMut(u8, u16, u16),
+    ) -> GlyphResult {
+        self.display_glyph_with(char, &RenderOptions::new().scale(scale), action)
+    }
+
+    /// Displays a glyph with every setting in `options` applied at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `options` - How the glyph should be displayed.
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn display_glyph_with<T: TryInto<u32>>(
+        &self,
+        char: T,
+        options: &RenderOptions,
+        mut action: impl FnMut(u8, u16, u16),
+    ) -> GlyphResult {
+        let (data, result) = self.lookup_or(char, options.fallback.unwrap_or(self.fallback));
+
+        let scale = options.scale.max(1) as u16;
+        let last_x = self.header.glyph_width.saturating_sub(1) as u8;
+        let last_y = self.header.glyph_height.saturating_sub(1) as u8;
+
+        for_each_bit(&self.header, data, options.bit_order, |bit, x, y| {
+            let x = if options.flip_x { last_x - x } else { x } as u16;
+            let y = if options.flip_y { last_y - y } else { y } as u16;
+
+            for i in 0..scale {
+                for j in 0..scale {
+                    action(bit, x * scale + j, y * scale + i);
+                }
+            }
+        });
+
+        result
+    }
+
+    /// Gets an iterator over the coordinates of every set bit in a glyph.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn glyph_pixels<T: TryInto<u32>>(&self, char: T) -> impl Iterator<Item = (u8, u8)> + '_ {
+        self.set_bits(self.resolve(char))
+    }
+
+    /// Gets an iterator over the coordinates of every set bit in a glyph's bitmap.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bitmap of the glyph.
+    fn set_bits<'b>(&self, data: &'b [u8]) -> impl Iterator<Item = (u8, u8)> + 'b {
+        let width = self.header.glyph_width as usize;
+        let bytes_in_row = self.bytes_per_row() as usize;
+
+        data.chunks(bytes_in_row)
+            .enumerate()
+            .flat_map(move |(y, row)| {
+                (0..width)
+                    .filter(move |x| (row[x / 8] >> (7 - x % 8)) & 1 == 1)
+                    .map(move |x| (x as u8, y as u8))
+            })
+    }
+
+    /// Gets an iterator over the rows of a glyph, with each row packed into a u32.
+    ///
+    /// The leftmost pixel is the most significant bit, and the bits past `glyph_width` are always 0.
+    /// Glyphs wider than 32 pixels only get their first 32 pixels.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn glyph_rows<T: TryInto<u32>>(&self, char: T) -> impl Iterator<Item = u32> + '_ {
+        let bytes_in_row = self.bytes_per_row() as usize;
+        let mask = u32::MAX
+            .checked_shl(32 - self.header.glyph_width.min(32))
+            .unwrap_or(0);
+
+        self.resolve(char).chunks(bytes_in_row).map(move |row| {
+            row.iter()
+                .take(4)
+                .enumerate()
+                .fold(0, |acc, (i, byte)| acc | ((*byte as u32) << (24 - i * 8)))
+                & mask
+        })
+    }
+
+    /// Gets the rows of a glyph that's at most 8 pixels wide and exactly `H` pixels tall,
+    /// with one byte for each row.
+    ///
+    /// The leftmost pixel is the most significant bit, and the bits past `glyph_width` are always 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    ///
+    /// Returns `None` if the glyphs are wider than 8 pixels or aren't `H` pixels tall.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    #[must_use]
+    pub fn glyph_rows_array<const H: usize, T: TryInto<u32>>(&self, char: T) -> Option<[u8; H]> {
+        if self.header.glyph_width > 8 || self.header.glyph_height as usize != H {
+            return None;
+        }
+
+        let mut rows = [0; H];
+        for (row, byte) in rows.iter_mut().zip(self.masked_bytes(self.resolve(char))) {
+            *row = byte;
+        }
+
+        Some(rows)
+    }
+
+    /// Gets the rows of a glyph in the common 8x16 console font size, see [`Font::glyph_rows_array`].
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    ///
+    /// Returns `None` if the glyphs are wider than 8 pixels or aren't 16 pixels tall.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    #[must_use]
+    pub fn glyph_u8x16<T: TryInto<u32>>(&self, char: T) -> Option<[u8; 16]> {
+        self.glyph_rows_array(char)
+    }
+
+    /// Displays a glyph with all of the blank padding around it trimmed off.
+    ///
+    /// The coordinates passed to `action` are relative to the top left of the trimmed glyph,
+    /// and the area of the glyph that was displayed is returned.
+    /// If the glyph is completely blank, the bounds will be zero sized and `action` is never called.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn display_glyph_trimmed<T: TryInto<u32>>(
+        &self,
+        char: T,
+        mut action: impl FnMut(u8, u8, u8),
+    ) -> Bounds {
+        let data = self.resolve(char);
+        let bounds = self.bounds_of(data).unwrap_or_default();
+
+        self.for_each_bit(data, |bit, x, y| {
+            if bounds.contains(x as u16, y as u16) {
+                action(bit, x - bounds.x as u8, y - bounds.y as u8);
+            }
+        });
+
+        bounds
+    }
+
+    /// Displays a whole string, moving along by one glyph for each character.
+    ///
+    /// A `\n` moves back to the start of the line and down by one glyph,
+    /// and a `\t` moves forward to the next multiple of [`TAB_WIDTH`] glyphs.
+    ///
+    /// Returns where the cursor ended up after the last character,
+    /// so more text can be displayed after it.
+    /// The cursor stops at `u16::MAX`, and pixels that would go past it aren't displayed.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The string to display.
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///   Unlike [`Font::display_glyph`], the x and the y are relative to the start of the string.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn display_str(&self, text: &str, action: impl FnMut(u8, u16, u16)) -> (u16, u16) {
+        self.display_str_spaced(text, 0, 0, action)
+    }
+
+    /// Displays a whole string like [`Font::display_str`], but with extra space between glyphs and lines.
+    ///
+    /// Negative spacing moves glyphs closer together, so they can overlap,
+    /// but the cursor never goes back past 0.
+    /// Tabs line up to multiples of [`TAB_WIDTH`] glyphs, including the letter spacing.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The string to display.
+    /// * `letter_spacing` - How many pixels to add between each glyph.
+    /// * `line_spacing` - How many pixels to add between each line.
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///   Unlike [`Font::display_glyph`], the x and the y are relative to the start of the string.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn display_str_spaced(
+        &self,
+        text: &str,
+        letter_spacing: i16,
+        line_spacing: i16,
+        action: impl FnMut(u8, u16, u16),
+    ) -> (u16, u16) {
+        self.layout_str(text, letter_spacing, line_spacing, |_| false, action)
+    }
+
+    /// Displays a whole string like [`Font::display_str`], but draws combining characters
+    /// over the glyph before them instead of giving them their own cell.
+    ///
+    /// A combining character with nothing before it on the line is displayed like any other.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The string to display.
+    /// * `is_combining` - A closure that says whether a character is a combining mark,
+    ///   like U+0301 for an acute accent.
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///   Unlike [`Font::display_glyph`], the x and the y are relative to the start of the string.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn display_str_combining(
+        &self,
+        text: &str,
+        is_combining: impl Fn(char) -> bool,
+        action: impl FnMut(u8, u16, u16),
+    ) -> (u16, u16) {
+        self.layout_str(text, 0, 0, is_combining, action)
+    }
+
+    /// Lays out and displays a string, which is shared by all of the `display_str` variants.
+    fn layout_str(
+        &self,
+        text: &str,
+        letter_spacing: i16,
+        line_spacing: i16,
+        is_combining: impl Fn(char) -> bool,
+        mut action: impl FnMut(u8, u16, u16),
+    ) -> (u16, u16) {
+        let (mut cursor_x, mut cursor_y) = (0u16, 0u16);
+        let advance_x = (self.header.glyph_width as i16).saturating_add(letter_spacing);
+        let advance_y = (self.header.glyph_height as i16).saturating_add(line_spacing);
+        let tab = advance_x.max(1).unsigned_abs().saturating_mul(TAB_WIDTH);
+
+        // Where the last glyph on this line was drawn, for combining characters to go over.
+        let mut base_x = None;
+
+        for char in text.chars() {
+            match char {
+                '\n' => {
+                    cursor_x = 0;
+                    cursor_y = cursor_y.saturating_add_signed(advance_y);
+                    base_x = None;
+                    continue;
+                }
+                '\t' => {
+                    cursor_x = next_tab_stop(cursor_x, tab);
+                    base_x = None;
+                    continue;
+                }
+                _ => (),
+            }
+
+            let combining = base_x.filter(|_| is_combining(char));
+            let origin_x = combining.unwrap_or(cursor_x);
+
+            self.display_glyph(char, |bit, x, y| {
+                // Pixels past the end of the coordinates are dropped, like in `display_glyph_clipped`.
+                if let (Some(x), Some(y)) = (
+                    origin_x.checked_add(x as u16),
+                    cursor_y.checked_add(y as u16),
+                ) {
+                    action(bit, x, y);
+                }
+            });
+
+            if combining.is_none() {
+                base_x = Some(cursor_x);
+                cursor_x = cursor_x.saturating_add_signed(advance_x);
+            }
+        }
+
+        (cursor_x, cursor_y)
+    }
+
+    /// Works out how big a string would be when displayed with [`Font::display_str`],
+    /// without displaying anything.
+    ///
+    /// Every glyph takes up a full cell, and tabs line up the same way they do when displaying.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The string to measure.
+    ///
+    /// Returns the width of the longest line and the height of all of the lines, in pixels.
+    #[must_use]
+    pub fn measure_str(&self, text: &str) -> (u16, u16) {
+        if text.is_empty() {
+            return (0, 0);
+        }
+
+        let glyph_width = self.header.glyph_width as u16;
+        let tab = glyph_width.max(1).saturating_mul(TAB_WIDTH);
+        let (mut width, mut lines, mut cursor_x) = (0, 1u16, 0u16);
+
+        for char in text.chars() {
+            cursor_x = match char {
+                '\n' => {
+                    lines = lines.saturating_add(1);
+                    0
+                }
+                '\t' => next_tab_stop(cursor_x, tab),
+                _ => cursor_x.saturating_add(glyph_width),
+            };
+
+            width = cursor_x.max(width);
+        }
+
+        (width, lines.saturating_mul(self.header.glyph_height as u16))
+    }
+
+    /// Gets the smallest area that contains every set bit of a glyph, without displaying it.
+    ///
+    /// The bounds are in the same coordinates that [`Font::display_glyph`] uses,
+    /// and the character is resolved the same way, including the fallback.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    ///
+    /// Returns `None` if the glyph is completely blank.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    #[must_use]
+    pub fn glyph_bounds<T: TryInto<u32>>(&self, char: T) -> Option<Bounds> {
+        self.bounds_of(self.resolve(char))
+    }
+
+    /// Resolves a character into the bitmap of the glyph that should be displayed for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    fn resolve<T: TryInto<u32>>(&self, char: T) -> &[u8] {
+        self.lookup(char).0
+    }
+
+    /// Resolves a character into a glyph bitmap, and also says whether the fallback was used.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    fn lookup<T: TryInto<u32>>(&self, char: T) -> (&[u8], GlyphResult) {
+        self.lookup_or(char, self.fallback)
+    }
+
+    /// Resolves a character into a glyph bitmap, with a different fallback than [`Font::fallback`].
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `fallback` - The character to use if `char` isn't in the font.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    fn lookup_or<T: TryInto<u32>>(&self, char: T, fallback: char) -> (&[u8], GlyphResult) {
+        match self.try_lookup(char, fallback) {
+            Ok(found) => found,
+            // There's nothing to display, rather than something wrong with the font.
+            Err(RenderError::GlyphDataTruncated) if self.header.length == 0 => {
+                (&[], GlyphResult::Missing)
+            }
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Resolves a character into a glyph bitmap, returning an error instead of panicking.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `fallback` - The character to use if `char` isn't in the font, which is usually [`Font::fallback`].
+    ///
+    /// # Errors
+    ///
+    /// * The same as [`Font::try_display_glyph`].
+    fn try_lookup<T: TryInto<u32>>(
+        &self,
+        char: T,
+        fallback: char,
+    ) -> Result<(&[u8], GlyphResult), RenderError> {
+        let (index, result) = self.try_lookup_index(char, fallback)?;
+        let data = self
+            .glyph_data(index)
+            .ok_or(RenderError::GlyphDataTruncated)?;
+
+        Ok((data, result))
+    }
+
+    /// Resolves a character into the index of the glyph that would be displayed for it,
+    /// including the fallback.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `fallback` - The character to use if `char` isn't in the font, which is usually [`Font::fallback`].
+    ///
+    /// # Errors
+    ///
+    /// * The same as [`Font::try_display_glyph`].
+    fn try_lookup_index<T: TryInto<u32>>(
+        &self,
+        char: T,
+        fallback: char,
+    ) -> Result<(u32, GlyphResult), RenderError> {
+        let char = TryInto::<u32>::try_into(char).map_err(|_| RenderError::InvalidCharacter)?;
+
+        [
+            (char, GlyphResult::Found),
+            (fallback as u32, GlyphResult::Fallback),
+        ]
+        .into_iter()
+        .filter_map(|(char, result)| Some((u32::try_from(self.glyph_index(char)?).ok()?, result)))
+        .chain([(0, GlyphResult::Missing)])
+        .find(|(index, _)| self.is_valid_index(*index as usize))
+        .ok_or(RenderError::GlyphDataTruncated)
+    }
+
+    /// Finds the smallest area that contains every set bit of a glyph.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bitmap of the glyph, from [`Font::glyph_data`].
+    ///
+    /// Returns `None` if the glyph is completely blank.
+    fn bounds_of(&self, data: &[u8]) -> Option<Bounds> {
+        let mut min = (u8::MAX, u8::MAX);
+        let mut max = (0, 0);
+
+        self.for_each_bit(data, |bit, x, y| {
+            if bit == 1 {
+                min = (min.0.min(x), min.1.min(y));
+                max = (max.0.max(x), max.1.max(y));
+            }
+        });
+
+        if min.0 > max.0 {
+            return None;
+        }
+
+        Some(Bounds {
+            x: min.0 as u16,
+            y: min.1 as u16,
+            w: (max.0 - min.0) as u16 + 1,
+            h: (max.1 - min.1) as u16 + 1,
+        })
+    }
+
+    /// Gets an iterator over the bytes of a glyph, with the padding bits at the end of each row cleared.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bitmap of the glyph, from [`Font::glyph_data`].
+    fn masked_bytes<'b>(&self, data: &'b [u8]) -> impl Iterator<Item = u8> + 'b {
+        let bytes_in_row = self.bytes_per_row() as usize;
+        let padding = bytes_in_row * 8 - self.header.glyph_width as usize;
+        let mask = u8::MAX << padding;
+
+        data.iter().enumerate().map(move |(i, byte)| {
+            if i % bytes_in_row == bytes_in_row - 1 {
+                byte & mask
+            } else {
+                *byte
+            }
+        })
+    }
+
+    /// Calls `action` for every bit of a glyph.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bitmap of the glyph, from [`Font::glyph_data`].
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    fn for_each_bit(&self, data: &[u8], action: impl FnMut(u8, u8, u8)) {
+        for_each_bit(&self.header, data, BitOrder::MsbFirst, action);
+    }
+
+    /// Loads a font with room for `N` characters in the unicode table,
+    /// instead of [`UNICODE_CAPACITY`].
+    ///
+    /// Smaller tables save memory, and bigger ones are needed for fonts with lots of characters.
+    ///
+    /// ```rust
+    /// use psf_rs::Font;
+    ///
+    /// let font = Font::<512>::try_load_with_capacity(include_bytes!("../test.psfu")).unwrap();
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the font file itself. This can be either psf1 or psf2.
+    ///
+    /// # Errors
+    ///
+    /// * The same as [`Font::try_load`], except [`LoadError::UnicodeTableFull`]
+    ///   is returned if the unicode table has more than `N` entries.
+    pub fn try_load_with_capacity(raw: &'a [u8]) -> Result<Self, LoadError> {
+        let mut font = Self::try_load_lazy_with_capacity(raw)?;
+        font.ensure_unicode_table()?;
+
+        Ok(font)
+    }
+
+    /// Loads a font without parsing the unicode table like [`Font::try_load_lazy`],
+    /// with room for `N` characters once it's parsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the font file itself. This can be either psf1 or psf2.
+    ///
+    /// # Errors
+    ///
+    /// * The same as [`Font::try_load`], except for [`LoadError::UnicodeTableFull`].
+    pub fn try_load_lazy_with_capacity(raw: &'a [u8]) -> Result<Self, LoadError> {
+        let header = Header::parse_any(raw)?;
+        let header_size = header.size as usize;
+        let Some(glyphs_end) = header.checked_glyphs_end() else {
+            return Err(LoadError::InvalidDimensions);
+        };
+
+        if raw.len() < glyphs_end {
+            return Err(LoadError::TruncatedGlyphData {
+                expected: glyphs_end,
+                actual: raw.len(),
+            });
+        }
+
+        Self::from_parts_unparsed(header, &raw[header_size..glyphs_end], &raw[glyphs_end..])
+    }
+
+    /// Puts a font together from a header and the data that comes after it,
+    /// for when they're read separately, like with [`Header::parse`].
+    ///
+    /// The capacity of the unicode table can't be worked out from the arguments,
+    /// so the type needs to be written out, like `let font: Font = Font::from_parts(...)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The header of the font.
+    /// * `glyphs` - The glyph data, which is [`Header::glyph_data_len`] bytes.
+    /// * `table` - The unicode table, which is everything after the glyphs.
+    ///   This is ignored if the header says there isn't one.
+    ///
+    /// # Errors
+    ///
+    /// * [`LoadError::InvalidDimensions`] if the glyphs are zero bytes in size,
+    ///   bigger than [`MAX_GLYPH_DIMENSION`], or their size doesn't match their width and height.
+    ///   This is also the error when there are too many glyphs for their size to fit in a `usize`.
+    /// * [`LoadError::TruncatedGlyphData`] if `glyphs` is too short.
+    /// * [`LoadError::UnicodeTableFull`] if the unicode table has more than `N` entries.
+    pub fn from_parts(
+        header: Header,
+        glyphs: &'a [u8],
+        table: &'a [u8],
+    ) -> Result<Self, LoadError> {
+        let mut font = Self::from_parts_unparsed(header, glyphs, table)?;
+        font.ensure_unicode_table()?;
+
+        Ok(font)
+    }
+
+    /// Puts a font together like [`Font::from_parts`], but without parsing the unicode table.
+    ///
+    /// # Errors
+    ///
+    /// * The same as [`Font::from_parts`], except for [`LoadError::UnicodeTableFull`].
+    fn from_parts_unparsed(
+        header: Header,
+        glyphs: &'a [u8],
+        table: &'a [u8],
+    ) -> Result<Self, LoadError> {
+        if !header.has_valid_dimensions() || header.checked_glyphs_end().is_none() {
+            return Err(LoadError::InvalidDimensions);
+        }
+
+        let Some(glyphs) = glyphs.get(..header.glyph_data_len()) else {
+            return Err(LoadError::TruncatedGlyphData {
+                expected: header.glyph_data_len(),
+                actual: glyphs.len(),
+            });
+        };
+
+        let table = &table[..Self::table_len(&header, table)];
 
-        let font = Self {
+        Ok(Self {
             header,
-            data,
-            unicode: Some(Self::parse_unicode_table(
-                &raw[(header.glyph_size * header.length) as usize..],
-            )),
+            #[cfg(feature = "alloc")]
+            data: Data::Borrowed(glyphs),
+            #[cfg(not(feature = "alloc"))]
+            data: glyphs,
+            #[cfg(feature = "alloc")]
+            table: Data::Borrowed(table),
+            #[cfg(not(feature = "alloc"))]
+            table,
+            unicode: None,
+            fallback: '?',
+            cache: None,
+        })
+    }
+
+    /// Parses the unicode table if it hasn't been already, for fonts loaded with [`Font::try_load_lazy`].
+    ///
+    /// Fonts loaded any other way already have it parsed, so this does nothing.
+    /// Fonts with a capacity of 0 never parse it, see [`Font`].
+    ///
+    /// # Errors
+    ///
+    /// * [`LoadError::UnicodeTableFull`] if the unicode table has more than `N` entries.
+    ///   The font can still be used, but the table stays unparsed.
+    pub fn ensure_unicode_table(&mut self) -> Result<(), LoadError> {
+        if N == 0 || !self.header.flags.unicode || self.unicode.is_some() {
+            return Ok(());
+        }
+
+        let table = &self.table;
+        self.unicode = Some(match self.header.format {
+            Version::Psf1 => Self::parse_psf1_unicode_table(table)?,
+            Version::Psf2 => Self::parse_unicode_table(table)?,
+        });
+
+        Ok(())
+    }
+
+    /// Gets how many bytes of `table` actually belong to the unicode table,
+    /// which is up to and including the terminator of the last glyph.
+    ///
+    /// If there aren't enough terminators, all of `table` is used.
+    fn table_len(header: &Header, table: &[u8]) -> usize {
+        if !header.flags.unicode {
+            return 0;
+        }
+
+        let terminator = header.format.entry_terminator();
+        let step = terminator.len();
+
+        let Some(last) = (header.length as usize).checked_sub(1) else {
+            return 0;
         };
 
-        assert!(
-            font.header.magic == MAGIC,
-            "header magic does not match, is this a psf2 font?"
-        );
+        table
+            .chunks_exact(step)
+            .enumerate()
+            .filter(|(_, chunk)| chunk == &terminator)
+            .nth(last)
+            .map_or(table.len(), |(i, _)| (i + 1) * step)
+    }
+}
+
+impl<'a> Font<'a> {
+    /// Parses the header of a font at compile time, like for checking its dimensions
+    /// with a const assertion.
+    ///
+    /// The rest of the font can't be loaded in a const context, since building the
+    /// unicode table isn't const.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the font file itself, or at least its header.
+    ///
+    /// # Panics
+    ///
+    /// * If the header can't be parsed, see [`Header::parse`] for the details.
+    ///   In a const context this is a compile error.
+    #[must_use]
+    pub const fn load_header_const(raw: &[u8]) -> Header {
+        match Header::parse_any(raw) {
+            Ok(header) => header,
+            Err(_) => panic!("failed to parse font header"),
+        }
+    }
+
+    /// Makes a font without any glyphs, as a placeholder for when there isn't a real one yet.
+    ///
+    /// Displaying anything with it does nothing, since there's nothing to display.
+    /// The glyphs are still 8x16, so text laid out with it takes up the usual amount of space.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use psf_rs::{Font, GlyphResult};
+    ///
+    /// static FONT: Font = Font::empty();
+    ///
+    /// assert_eq!(FONT.display_glyph('A', |_, _, _| unreachable!()), GlyphResult::Missing);
+    /// ```
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self {
+            header: Header {
+                magic: PSF2_MAGIC,
+                format: Version::Psf2,
+                version: PSF2_VERSION,
+                size: HEADER_SIZE as u32,
+                flags: Flags { unicode: false },
+                length: 0,
+                glyph_size: 16,
+                glyph_height: 16,
+                glyph_width: 8,
+            },
+            #[cfg(feature = "alloc")]
+            data: Data::Borrowed(&[]),
+            #[cfg(not(feature = "alloc"))]
+            data: &[],
+            #[cfg(feature = "alloc")]
+            table: Data::Borrowed(&[]),
+            #[cfg(not(feature = "alloc"))]
+            table: &[],
+            unicode: None,
+            fallback: '?',
+            cache: None,
+        }
+    }
+
+    /// Loads a font.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the font file itself.
+    ///
+    /// # Panics
+    ///
+    /// * If the font can't be loaded, see [`Font::try_load`] for the details.
+    #[must_use]
+    pub fn load(raw: &'a [u8]) -> Self {
+        match Self::try_load(raw) {
+            Ok(font) => font,
+            Err(err) => panic!("failed to load font: {err}"),
+        }
+    }
+
+    /// Loads a font, returning an error instead of panicking if it's malformed.
+    ///
+    /// Invalid UTF-8 in a psf2 unicode table isn't an error. Each bad character is skipped
+    /// up to the first byte that can't be part of it, so the characters after it still load.
+    /// A character that's cut off at the end of an entry is skipped too.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the font file itself. This can be either psf1 or psf2.
+    ///
+    /// # Errors
+    ///
+    /// * [`LoadError::TooShort`] if there isn't enough data for the header.
+    /// * [`LoadError::BadMagic`] if the magic doesn't match.
+    /// * [`LoadError::UnsupportedVersion`] if the psf2 version isn't [`PSF2_VERSION`].
+    /// * [`LoadError::InvalidHeaderSize`] if the psf2 header says it's smaller than [`HEADER_SIZE`].
+    /// * [`LoadError::InvalidDimensions`] if the glyphs are zero bytes in size,
+    ///   bigger than [`MAX_GLYPH_DIMENSION`], or their size doesn't match their width and height.
+    ///   This is also the error when there are too many glyphs for their size to fit in a `usize`.
+    /// * [`LoadError::TruncatedGlyphData`] if there isn't enough data for every glyph.
+    /// * [`LoadError::UnicodeTableFull`] if the unicode table has more than [`UNICODE_CAPACITY`] entries.
+    pub fn try_load(raw: &'a [u8]) -> Result<Self, LoadError> {
+        Self::try_load_with_capacity(raw)
+    }
+
+    /// Loads a font with some extra settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the font file itself. This can be either psf1 or psf2.
+    /// * `options` - How to load the font.
+    ///
+    /// # Errors
+    ///
+    /// * The same as [`Font::try_load`].
+    /// * [`LoadError::TrailingData`] if the options are strict and there's anything after the font.
+    pub fn try_load_with(raw: &'a [u8], options: &LoadOptions) -> Result<Self, LoadError> {
+        let font = Self::try_load(raw)?;
+
+        match raw.len() - font.byte_len() {
+            trailing @ 1.. if options.strict => Err(LoadError::TrailingData(trailing)),
+            _ => Ok(font),
+        }
+    }
+
+    /// Loads a font without parsing its unicode table, which is much faster
+    /// for fonts with big tables that only ever need to display ASCII.
+    ///
+    /// Characters can still be looked up before the table is parsed,
+    /// but that searches the whole table every time.
+    /// Use [`Font::ensure_unicode_table`] to parse the table once it's needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes for the font file itself. This can be either psf1 or psf2.
+    ///
+    /// # Errors
+    ///
+    /// * The same as [`Font::try_load`], except for [`LoadError::UnicodeTableFull`].
+    pub fn try_load_lazy(raw: &'a [u8]) -> Result<Self, LoadError> {
+        Self::try_load_lazy_with_capacity(raw)
+    }
+
+    /// Loads every font in a blob of fonts that were put back to back, like with `cat`.
+    ///
+    /// Each font borrows its own part of `raw`, and they can be a mix of psf1 and psf2.
+    /// Loading stops after the first font that fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The raw bytes of all of the fonts.
+    ///
+    /// # Errors
+    ///
+    /// * The same as [`Font::try_load`], for each font.
+    pub fn load_many(raw: &'a [u8]) -> impl Iterator<Item = Result<Self, LoadError>> {
+        let mut rest = Some(raw);
+
+        core::iter::from_fn(move || {
+            let raw = rest.filter(|raw| !raw.is_empty())?;
+
+            match Self::try_load(raw) {
+                Ok(font) => {
+                    rest = Some(&raw[font.byte_len()..]);
+                    Some(Ok(font))
+                }
+                Err(err) => {
+                    rest = None;
+                    Some(Err(err))
+                }
+            }
+        })
+    }
+}
+
+/// Makes a font without any glyphs, see [`Font::empty`].
+impl Default for Font<'_> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// Loads a font with [`Font::try_load_with_capacity`].
+///
+/// There's no `From` because loading can fail, and panicking in a conversion would be surprising.
+impl<'a, const N: usize> TryFrom<&'a [u8]> for Font<'a, N> {
+    type Error = LoadError;
+
+    fn try_from(raw: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::try_load_with_capacity(raw)
+    }
+}
+
+/// Works out what kind of font some data is from its magic bytes, without loading it.
+///
+/// This only looks at the first 4 bytes, so it doesn't mean the font will actually load.
+///
+/// # Arguments
+///
+/// * `raw` - The start of the font file, which can be any length.
+#[must_use]
+pub fn detect_format(raw: &[u8]) -> Format {
+    if raw.starts_with(&PSF2_MAGIC) {
+        Format::Psf2
+    } else if raw.starts_with(&PSF1_MAGIC) {
+        Format::Psf1
+    } else if raw.starts_with(&GZIP_MAGIC) {
+        Format::Gzip
+    } else {
+        Format::Unknown
+    }
+}
+
+/// Gets the size in bytes of a glyph that is `width` by `height` pixels.
+///
+/// Each row is padded up to a whole byte, so an 8x16 glyph is 16 bytes
+/// while a 9x16 glyph is 32.
+///
+/// Returns `None` if the size is too big to fit in a `u32`.
+#[must_use]
+pub const fn glyph_size_for(width: u32, height: u32) -> Option<u32> {
+    width.div_ceil(8).checked_mul(height)
+}
+
+/// Works out where a tab moves the cursor to, which is the next multiple of `tab`.
+///
+/// This stops at `u16::MAX`, the same as the cursor does for every other character.
+const fn next_tab_stop(cursor: u16, tab: u16) -> u16 {
+    match (cursor / tab + 1).checked_mul(tab) {
+        Some(stop) => stop,
+        None => u16::MAX,
+    }
+}
+
+/// Calls `action` for every bit of a glyph, which is laid out the way `header` says.
+///
+/// # Arguments
+///
+/// * `header` - The header of the font the glyph is from.
+/// * `data` - The bitmap of the glyph.
+/// * `order` - Which end of each byte the leftmost pixel is in.
+/// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+fn for_each_bit(header: &Header, data: &[u8], order: BitOrder, mut action: impl FnMut(u8, u8, u8)) {
+    let bytes_in_row = header.glyph_width.div_ceil(8) as usize;
+    let width = header.glyph_width as usize;
+
+    for (i, row) in data.chunks(bytes_in_row).enumerate() {
+        for (j, byte) in row.iter().enumerate() {
+            // Only the last byte in a row can have padding, so every other one is all 8 bits.
+            let bits = width.saturating_sub(j * 8).min(8);
+
+            for k in 0..bits as u8 {
+                let shift = match order {
+                    BitOrder::MsbFirst => 7 - k,
+                    BitOrder::LsbFirst => k,
+                };
+
+                // Bit is a u8 that is always either a 0 or a 1.
+                // "But why not use a boolean?" I hear you ask.
+                // Every variable in rust is always at least one byte in size,
+                // So it doesn't do much for saving memory.
+                action((byte >> shift) & 1, (j as u8 * 8) + k, i as u8);
+            }
+        }
+    }
+}
+
+/// Decodes the UTF-8 character at the start of `bytes` straight to a codepoint.
+///
+/// This catches everything `str::from_utf8` would, like overlong encodings and surrogates.
+/// An invalid character only takes up the bytes up to where it went wrong,
+/// so a valid character right after it isn't swallowed.
+///
+/// Returns how many bytes the character takes up, along with its codepoint if it's valid,
+/// or `None` if `bytes` is empty or the character got cut off at the end.
+const fn decode_utf8(bytes: &[u8]) -> Option<(usize, Option<u32>)> {
+    let Some((lead, rest)) = bytes.split_first() else {
+        return None;
+    };
+
+    let (len, mut codepoint) = match *lead {
+        byte @ 0x00..=0x7f => (1, byte as u32),
+        byte @ 0xc0..=0xdf => (2, (byte & 0x1f) as u32),
+        byte @ 0xe0..=0xef => (3, (byte & 0x0f) as u32),
+        byte @ 0xf0..=0xf7 => (4, (byte & 0x07) as u32),
+        // Continuation bytes that don't have a lead byte before them,
+        // or bytes that can't be in UTF-8 at all.
+        _ => return Some((1, None)),
+    };
+
+    let mut i = 0;
+    while i < len - 1 {
+        // Only cut off if every byte that is there belongs to the character.
+        if i == rest.len() {
+            return None;
+        }
 
-        font
+        if rest[i] & 0xc0 != 0x80 {
+            return Some((1 + i, None));
+        }
+
+        codepoint = (codepoint << 6) | (rest[i] & 0x3f) as u32;
+        i += 1;
+    }
+
+    if codepoint < [0, 0x80, 0x800, 0x1_0000][len - 1] || char::from_u32(codepoint).is_none() {
+        return Some((len, None));
+    }
+
+    Some((len, Some(codepoint)))
+}
+
+/// Adds a character to a unicode table, keeping it sorted.
+///
+/// If the character is already there, the new glyph replaces the old one.
+///
+/// # Errors
+///
+/// * [`LoadError::UnicodeTableFull`] if the table is already full.
+fn insert_entry<const N: usize>(
+    table: &mut UnicodeTable<N>,
+    codepoint: u32,
+    glyph: usize,
+) -> Result<(), LoadError> {
+    match table.binary_search_by_key(&codepoint, |(codepoint, _)| *codepoint) {
+        Ok(i) => {
+            table[i].1 = glyph;
+            Ok(())
+        }
+        Err(i) => table
+            .insert(i, (codepoint, glyph))
+            .map_err(|_| LoadError::UnicodeTableFull),
     }
 }
 
-/// Converts an array of u8's into one u32.
-const fn as_u32_le(array: &[u8]) -> u32 {
-    assert!(
-        array.len() > 3,
-        "`array` needs to have four elements or more"
-    );
+/// Gets the four bytes of a header field.
+const fn field(raw: &[u8; HEADER_SIZE], at: usize) -> [u8; 4] {
+    [raw[at], raw[at + 1], raw[at + 2], raw[at + 3]]
+}
+
+/// Converts four little endian bytes into one u32.
+const fn as_u32_le(bytes: [u8; 4]) -> u32 {
+    u32::from_le_bytes(bytes)
+}
 
-    (array[0] as u32)
-        + ((array[1] as u32) << 8u32)
-        + ((array[2] as u32) << 16u32)
-        + ((array[3] as u32) << 24u32)
+/// Converts one u32 into four little endian bytes, which is what psf2 uses
+/// no matter what the host is.
+///
+/// Anything that writes fonts should go through this, rather than `to_ne_bytes`.
+const fn as_u32_le_bytes(value: u32) -> [u8; 4] {
+    value.to_le_bytes()
 }
@@ -0,0 +1,98 @@
+//! Fonts that own their data, so the original bytes don't need to stay around.
+
+use alloc::{borrow::Cow, vec::Vec};
+
+use crate::{EditError, Font, FontBuilder, WriteError, UNICODE_CAPACITY};
+
+/// A font that owns all of its data.
+///
+/// This is just a [`Font`] that doesn't borrow from anything,
+/// so it can be used in exactly the same ways.
+pub type FontOwned<const N: usize = UNICODE_CAPACITY> = Font<'static, N>;
+
+impl<const N: usize> Font<'_, N> {
+    /// Copies the glyph data out of the original bytes, so they can be dropped.
+    ///
+    /// Fonts loaded normally borrow the bytes they're loaded from,
+    /// which makes them awkward to keep around when those bytes are temporary.
+    ///
+    /// The index cache is borrowed too, so it isn't kept, see [`Font::set_index_cache`].
+    #[must_use]
+    pub fn into_owned(self) -> FontOwned<N> {
+        Font {
+            header: self.header,
+            data: Cow::Owned(self.data.into_owned()),
+            table: Cow::Owned(self.table.into_owned()),
+            unicode: self.unicode,
+            fallback: self.fallback,
+            cache: None,
+        }
+    }
+
+    /// Writes the font back out as psf2, which also converts psf1 fonts.
+    ///
+    /// The glyphs and what every character maps to stay the same,
+    /// but the unicode table isn't necessarily in the same order as it was originally.
+    ///
+    /// # Errors
+    ///
+    /// * The same as [`FontBuilder::write_to`], which only happens if the header
+    ///   doesn't match the glyph data.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, WriteError> {
+        let builder = FontBuilder::new(
+            self.header.glyph_width,
+            self.header.glyph_height,
+            &self.data,
+        );
+
+        if !self.header.flags.unicode {
+            return builder.build();
+        }
+
+        let unicode: Vec<(char, usize)> = self.unicode_entries().collect();
+
+        let mut sequences = Vec::new();
+        self.for_each_sequence(|glyph, sequence| sequences.push((sequence.to_vec(), glyph)));
+
+        let sequences: Vec<(&[char], usize)> = sequences
+            .iter()
+            .map(|(sequence, glyph)| (sequence.as_slice(), *glyph))
+            .collect();
+
+        builder.unicode(&unicode).sequences(&sequences).build()
+    }
+}
+
+impl<const N: usize> FontOwned<N> {
+    /// Replaces the bitmap of a glyph, like for editing a font after loading it.
+    ///
+    /// The glyph data is copied first if it's still borrowed,
+    /// and the change can be saved with [`Font::to_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the glyph in the font, NOT a character.
+    /// * `bitmap` - The new bitmap, in the same layout as [`Font::glyph_data`].
+    ///
+    /// # Errors
+    ///
+    /// * [`EditError::IndexOutOfBounds`] if there's no glyph at `index`.
+    /// * [`EditError::WrongLength`] if `bitmap` isn't exactly one glyph long.
+    pub fn set_glyph(&mut self, index: usize, bitmap: &[u8]) -> Result<(), EditError> {
+        let size = self.header.glyph_size as usize;
+
+        if index >= self.header.length as usize {
+            return Err(EditError::IndexOutOfBounds);
+        }
+
+        if bitmap.len() != size {
+            return Err(EditError::WrongLength {
+                expected: size,
+                actual: bitmap.len(),
+            });
+        }
+
+        self.data.to_mut()[index * size..(index + 1) * size].copy_from_slice(bitmap);
+        Ok(())
+    }
+}
@@ -0,0 +1,370 @@
+//! Helpers for rendering glyphs straight into buffers.
+
+use core::fmt::{self, Write};
+
+use crate::Font;
+
+impl<const N: usize> Font<'_, N> {
+    /// Gets how many bytes [`Font::render_glyph_to`] needs for a glyph,
+    /// when the stride is [`Font::bytes_per_row`].
+    #[must_use]
+    pub const fn glyph_buffer_len_1bpp(&self) -> usize {
+        self.bytes_per_row() as usize * self.header.glyph_height as usize
+    }
+
+    /// Gets how many bytes [`Font::render_glyph_gray`] needs for a glyph,
+    /// when the stride is the width of the glyph.
+    #[must_use]
+    pub const fn glyph_buffer_len_8bpp(&self) -> usize {
+        self.header.glyph_width as usize * self.header.glyph_height as usize
+    }
+
+    /// Gets how many pixels [`Font::render_glyph_rgba`] needs for a glyph,
+    /// when the stride is the width of the glyph.
+    ///
+    /// This is the length of the `u32` buffer, so it's 4 times as many bytes.
+    #[must_use]
+    pub const fn glyph_buffer_len_rgba(&self) -> usize {
+        self.glyph_buffer_len_8bpp()
+    }
+
+    /// Renders a glyph into a buffer with 1 bit per pixel.
+    ///
+    /// Each row is packed the same way psf does it, with the leftmost pixel in the
+    /// most significant bit, and any padding bits at the end of the row cleared.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `buf` - The buffer to render into.
+    /// * `stride` - The amount of bytes between the start of each row in `buf`.
+    ///
+    /// Returns the amount of rows written,
+    /// or `None` if `buf` is too small or `stride` is too short to hold a row.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn render_glyph_to<T: TryInto<u32>>(
+        &self,
+        char: T,
+        buf: &mut [u8],
+        stride: usize,
+    ) -> Option<usize> {
+        let data = self.resolve(char);
+        let bytes_in_row = self.bytes_per_row() as usize;
+        let rows = self.header.glyph_height as usize;
+
+        if stride < bytes_in_row || buf.len() < required_len(rows, stride, bytes_in_row)? {
+            return None;
+        }
+
+        let padding = bytes_in_row * 8 - self.header.glyph_width as usize;
+        let mask = u8::MAX << padding;
+
+        // Glyphs without any data, like in `Font::empty`, are left blank.
+        for i in 0..rows {
+            buf[i * stride..i * stride + bytes_in_row].fill(0);
+        }
+
+        for (i, row) in data.chunks(bytes_in_row).take(rows).enumerate() {
+            let target = &mut buf[i * stride..i * stride + bytes_in_row];
+
+            target.copy_from_slice(row);
+            target[bytes_in_row - 1] &= mask;
+        }
+
+        Some(rows)
+    }
+
+    /// Renders a glyph into a buffer with 1 byte per pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `buf` - The buffer to render into.
+    /// * `stride` - The amount of bytes between the start of each row in `buf`.
+    /// * `on` - The value written for set bits.
+    /// * `off` - The value written for clear bits.
+    ///
+    /// Returns the amount of rows written,
+    /// or `None` if `buf` is too small or `stride` is too short to hold a row.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn render_glyph_gray<T: TryInto<u32>>(
+        &self,
+        char: T,
+        buf: &mut [u8],
+        stride: usize,
+        on: u8,
+        off: u8,
+    ) -> Option<usize> {
+        let data = self.resolve(char);
+        let width = self.header.glyph_width as usize;
+        let rows = self.header.glyph_height as usize;
+
+        if stride < width || buf.len() < required_len(rows, stride, width)? {
+            return None;
+        }
+
+        // Glyphs without any data, like in `Font::empty`, are left blank.
+        for i in 0..rows {
+            buf[i * stride..i * stride + width].fill(off);
+        }
+
+        self.for_each_bit(data, |bit, x, y| {
+            buf[y as usize * stride + x as usize] = if bit == 1 { on } else { off };
+        });
+
+        Some(rows)
+    }
+
+    /// Renders a glyph scaled up into a buffer with 1 byte per pixel, smoothing out the edges.
+    ///
+    /// Each pixel is scaled up into a block, the same as [`Font::display_glyph_scaled`].
+    /// Pixels on the border of a block are then averaged with the neighbouring blocks
+    /// they touch, so edges get values in between and the inside of a block is left alone.
+    /// Set bits are 255 and clear bits are 0, like [`Font::render_glyph_gray`].
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `scale` - How many times bigger the glyph should be. A scale of 0 or 1 doesn't smooth anything.
+    /// * `buf` - The buffer to render into.
+    /// * `stride` - The amount of bytes between the start of each row in `buf`.
+    ///
+    /// Returns the amount of rows written,
+    /// or `None` if `buf` is too small or `stride` is too short to hold a row.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn render_glyph_scaled_smooth<T: TryInto<u32>>(
+        &self,
+        char: T,
+        scale: u8,
+        buf: &mut [u8],
+        stride: usize,
+    ) -> Option<usize> {
+        let data = self.resolve(char);
+        let scale = scale.max(1) as usize;
+        let bytes_in_row = self.bytes_per_row() as usize;
+        let width = self.header.glyph_width as usize;
+        let height = self.header.glyph_height as usize;
+        let (scaled_width, rows) = (width * scale, height * scale);
+
+        if stride < scaled_width || buf.len() < required_len(rows, stride, scaled_width)? {
+            return None;
+        }
+
+        // Anything outside of the glyph, or missing from its data, counts as clear.
+        let bit = |x: usize, y: usize| -> u16 {
+            if x >= width || y >= height {
+                return 0;
+            }
+
+            data.get(y * bytes_in_row + x / 8)
+                .map_or(0, |byte| (byte >> (7 - x % 8) & 1).into())
+        };
+
+        // Which neighbouring block the pixel at `i` in a block touches, if any.
+        let neighbour = |i: usize, at: usize| match i {
+            _ if scale == 1 => at,
+            0 => at.wrapping_sub(1),
+            i if i == scale - 1 => at + 1,
+            _ => at,
+        };
+
+        for y in 0..rows {
+            let (source_y, block_y) = (y / scale, y % scale);
+            let other_y = neighbour(block_y, source_y);
+
+            for x in 0..scaled_width {
+                let (source_x, block_x) = (x / scale, x % scale);
+                let other_x = neighbour(block_x, source_x);
+
+                let sum = bit(source_x, source_y)
+                    + bit(other_x, source_y)
+                    + bit(source_x, other_y)
+                    + bit(other_x, other_y);
+
+                buf[y * stride + x] = (sum * 255 / 4) as u8;
+            }
+        }
+
+        Some(rows)
+    }
+
+    /// Renders a glyph into a buffer of packed `0xAARRGGBB` pixels.
+    ///
+    /// Anything that doesn't fit into `buf` or goes past `stride_px` gets clipped,
+    /// so this can be used to render into a small part of a bigger surface.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `buf` - The buffer to render into.
+    /// * `stride_px` - The amount of pixels between the start of each row in `buf`.
+    /// * `fg` - The color written for set bits.
+    /// * `bg` - The color written for clear bits.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn render_glyph_rgba<T: TryInto<u32>>(
+        &self,
+        char: T,
+        buf: &mut [u32],
+        stride_px: usize,
+        fg: u32,
+        bg: u32,
+    ) {
+        self.for_each_bit(self.resolve(char), |bit, x, y| {
+            if x as usize >= stride_px {
+                return;
+            }
+
+            if let Some(pixel) = buf.get_mut(y as usize * stride_px + x as usize) {
+                *pixel = if bit == 1 { fg } else { bg };
+            }
+        });
+    }
+
+    /// Renders a glyph into a buffer of vertical columns, which is what SSD1306 style
+    /// displays expect.
+    ///
+    /// Each byte is 8 pixels stacked on top of each other, with the top one in the
+    /// least significant bit. The glyph is split into bands of 8 rows, and each band is
+    /// one byte for every column, from left to right.
+    /// If the height isn't a multiple of 8, the bits below the last row are cleared.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `buf` - The buffer to render into.
+    ///
+    /// Returns the amount of bytes written, which is the width times the amount of bands,
+    /// or `None` if `buf` is too small.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn render_glyph_column_major<T: TryInto<u32>>(
+        &self,
+        char: T,
+        buf: &mut [u8],
+    ) -> Option<usize> {
+        let width = self.header.glyph_width as usize;
+        let len = width * self.header.glyph_height.div_ceil(8) as usize;
+        let buf = buf.get_mut(..len)?;
+
+        buf.fill(0);
+        self.for_each_bit(self.resolve(char), |bit, x, y| {
+            buf[(y / 8) as usize * width + x as usize] |= bit << (y % 8);
+        });
+
+        Some(len)
+    }
+
+    /// Writes a glyph out as text, with one line per row, which is handy for quickly previewing glyphs.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `w` - Where to write the text to.
+    /// * `on` - The character written for set bits, like `'█'`.
+    /// * `off` - The character written for clear bits, like `' '`.
+    ///
+    /// # Errors
+    ///
+    /// * If writing to `w` fails.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn write_glyph_art<T: TryInto<u32>>(
+        &self,
+        char: T,
+        w: &mut impl Write,
+        on: char,
+        off: char,
+    ) -> fmt::Result {
+        let last_x = self.header.glyph_width.saturating_sub(1);
+        let mut result = Ok(());
+
+        self.for_each_bit(self.resolve(char), |bit, x, _| {
+            if result.is_err() {
+                return;
+            }
+
+            result = w.write_char(if bit == 1 { on } else { off });
+
+            if result.is_ok() && x as u32 == last_x {
+                result = w.write_char('\n');
+            }
+        });
+
+        result
+    }
+
+    /// Writes a glyph out as a binary (P4) PBM image, which is handy for checking glyphs
+    /// in an image viewer.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - A character or integer, the same as in [`Font::display_glyph`].
+    /// * `buf` - The buffer to write the image into.
+    ///
+    /// Returns the amount of bytes written, or `None` if `buf` is too small.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn glyph_to_pbm<T: TryInto<u32>>(&self, char: T, buf: &mut [u8]) -> Option<usize> {
+        let mut header = SliceWriter { buf, len: 0 };
+        write!(
+            header,
+            "P4\n{} {}\n",
+            self.header.glyph_width, self.header.glyph_height
+        )
+        .ok()?;
+
+        let start = header.len;
+        let bytes_in_row = self.bytes_per_row() as usize;
+        let rows = self.render_glyph_to(char, &mut buf[start..], bytes_in_row)?;
+
+        Some(start + rows * bytes_in_row)
+    }
+}
+
+/// Works out how long a buffer has to be to hold `rows` rows of `row_len` bytes each,
+/// with `stride` bytes between the start of each row.
+///
+/// Returns `None` if there aren't any rows, or if the length doesn't fit in a `usize`.
+fn required_len(rows: usize, stride: usize, row_len: usize) -> Option<usize> {
+    rows.checked_sub(1)?
+        .checked_mul(stride)?
+        .checked_add(row_len)
+}
+
+/// Writes formatted text into a fixed buffer, failing if it doesn't fit.
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let target = self
+            .buf
+            .get_mut(self.len..self.len + s.len())
+            .ok_or(fmt::Error)?;
+
+        target.copy_from_slice(s.as_bytes());
+        self.len += s.len();
+
+        Ok(())
+    }
+}
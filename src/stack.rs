@@ -0,0 +1,79 @@
+//! Falling back across several fonts, like a terminal does for scripts that one font can't cover.
+
+use crate::{Font, UNICODE_CAPACITY};
+
+/// An ordered list of fonts, where each character is displayed with the first font that has it.
+///
+/// If none of the fonts have a character, the first font is used, along with its fallback.
+///
+/// # Example
+///
+/// ```rust
+/// use psf_rs::{Font, FontStack};
+///
+/// let fonts = [Font::load(include_bytes!("../test.psfu"))];
+/// let stack = FontStack::new(&fonts);
+///
+/// let (width, height) = stack.display_glyph('A', |bit, x, y| {
+///     // Stuff
+/// });
+/// assert_eq!((width, height), (8, 16));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct FontStack<'a, const N: usize = UNICODE_CAPACITY> {
+    fonts: &'a [Font<'a, N>],
+}
+
+impl<'a, const N: usize> FontStack<'a, N> {
+    /// Makes a stack out of fonts, going from the first one to try to the last.
+    #[must_use]
+    pub const fn new(fonts: &'a [Font<'a, N>]) -> Self {
+        Self { fonts }
+    }
+
+    /// Gets the fonts in the stack, in the order they're tried.
+    #[must_use]
+    pub const fn fonts(&self) -> &'a [Font<'a, N>] {
+        self.fonts
+    }
+
+    /// Gets the font a character would be displayed with.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - The character to find a font for.
+    ///
+    /// Returns `None` if the stack is empty.
+    #[must_use]
+    pub fn font_for(&self, char: char) -> Option<&'a Font<'a, N>> {
+        self.fonts
+            .iter()
+            .find(|font| font.has_glyph(char))
+            .or_else(|| self.fonts.first())
+    }
+
+    /// Displays a glyph with whichever font has it, see [`Font::display_glyph`].
+    ///
+    /// Fonts in a stack can have different sizes, so the x and the y only go up to
+    /// the size of the font that was used.
+    ///
+    /// # Arguments
+    ///
+    /// * `char` - The character to display.
+    /// * `action` - A closure that takes in 3 values, the bit (always 0 or 1), the x, and the y.
+    ///
+    /// Returns the width and height of the glyph that was displayed,
+    /// which is `(0, 0)` if the stack is empty.
+    ///
+    /// # Panics
+    ///
+    /// * In the same cases as [`Font::display_glyph`].
+    pub fn display_glyph(&self, char: char, action: impl FnMut(u8, u8, u8)) -> (u32, u32) {
+        let Some(font) = self.font_for(char) else {
+            return (0, 0);
+        };
+
+        font.display_glyph(char, action);
+        (font.width(), font.height())
+    }
+}
@@ -1,8 +1,25 @@
 #![cfg(test)]
 
-use crate::Font;
+extern crate std;
+
+use std::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    detect_format, glyph_size_for, BitOrder, Bounds, DiffEntry, Font, FontBuilder, FontStack,
+    Format, GlyphCache, GlyphResult, Header, IndexCache, LoadError, LoadOptions, Lookup,
+    RenderError, RenderOptions, Rotation, StaticFont, Transform, ValidationWarning, Version,
+    WriteError, HEADER_SIZE, MAX_GLYPH_DIMENSION, PSF1_MAGIC, PSF2_MAGIC,
+};
+
+const FONT: &[u8] = include_bytes!("../test.psfu");
+const PSF1_FONT: &[u8] = include_bytes!("../test.psf");
 
 #[test]
+// The second omega is the ohm sign, which is a different codepoint with the same glyph.
+#[allow(clippy::items_after_statements, clippy::unicode_not_nfc)]
 fn glyph_index() {
     let font = Font::load(include_bytes!("../test.psfu"));
 
@@ -24,3 +41,2444 @@ fn glyph_index() {
 
     assert_ne!(OMEGA_1, OMEGA_2);
 }
+
+#[test]
+fn glyph_index_out_of_range() {
+    let font = Font::load(FONT);
+
+    assert_eq!(font.glyph_index(0x10_ffff), None);
+    assert_eq!(font.glyph_index(0xd800), None);
+}
+
+#[test]
+fn try_load() {
+    assert!(Font::try_load(FONT).is_ok());
+    assert_eq!(
+        Font::try_load(&FONT[..16]).unwrap_err(),
+        LoadError::TooShort
+    );
+    assert_eq!(
+        Font::try_load(&FONT[..31]).unwrap_err(),
+        LoadError::TooShort
+    );
+    assert_eq!(
+        Font::try_load(&FONT[..1024]).unwrap_err(),
+        LoadError::TruncatedGlyphData {
+            expected: 32 + 256 * 16,
+            actual: 1024
+        }
+    );
+
+    let mut bad_magic = [0; 32];
+    bad_magic.copy_from_slice(&FONT[..32]);
+    bad_magic[0] = 0;
+    assert_eq!(Font::try_load(&bad_magic).unwrap_err(), LoadError::BadMagic);
+
+    let mut bad_version = [0; 32];
+    bad_version.copy_from_slice(&FONT[..32]);
+    bad_version[4] = 1;
+    assert_eq!(
+        Font::try_load(&bad_version).unwrap_err(),
+        LoadError::UnsupportedVersion(1)
+    );
+
+    let mut small_header = [0; 32];
+    small_header.copy_from_slice(&FONT[..32]);
+    small_header[0x8] = 16;
+    assert_eq!(
+        Font::try_load(&small_header).unwrap_err(),
+        LoadError::InvalidHeaderSize(16)
+    );
+
+    let mut empty_glyphs = [0; 32];
+    empty_glyphs.copy_from_slice(&FONT[..32]);
+    empty_glyphs[0x14] = 0;
+    assert_eq!(
+        Font::try_load(&empty_glyphs).unwrap_err(),
+        LoadError::InvalidDimensions
+    );
+
+    // A width of 0 also means the glyphs are 0 bytes.
+    let mut zero_width = FONT.to_vec();
+    zero_width[0x1c] = 0;
+    assert_eq!(
+        Font::try_load(&zero_width).unwrap_err(),
+        LoadError::InvalidDimensions
+    );
+
+    // 1024x1 glyphs are the right size, but are too wide to display.
+    // The builder refuses to write them, so the header is changed afterwards.
+    let glyphs = [0; 128];
+    let mut buf = [0; 256];
+    let len = FontBuilder::new(8, 128, &glyphs)
+        .write_to(&mut buf)
+        .unwrap();
+    buf[0x18..0x1c].copy_from_slice(&1u32.to_le_bytes());
+    buf[0x1c..0x20].copy_from_slice(&1024u32.to_le_bytes());
+    assert_eq!(
+        Font::try_load(&buf[..len]).unwrap_err(),
+        LoadError::InvalidDimensions
+    );
+
+    // 8x16 glyphs should be 16 bytes, not 12.
+    let mut inconsistent = FONT.to_vec();
+    inconsistent[0x14] = 12;
+    assert_eq!(
+        Font::try_load(&inconsistent).unwrap_err(),
+        LoadError::InvalidDimensions
+    );
+}
+
+#[test]
+fn try_load_lazy() {
+    let eager = Font::load(FONT);
+    let mut font = Font::try_load_lazy(FONT).unwrap();
+
+    for char in ['A', 'μ', '╝', 'Ω', '😀'] {
+        assert_eq!(
+            font.glyph_index(char as u32),
+            eager.glyph_index(char as u32)
+        );
+    }
+    // The raw table has a few characters that are mapped twice, which parsing only keeps once.
+    let entries: Vec<_> = font.unicode_entries().collect();
+    assert!(eager
+        .unicode_entries()
+        .all(|entry| entries.contains(&entry)));
+
+    font.ensure_unicode_table().unwrap();
+    assert!(font.unicode_entries().eq(eager.unicode_entries()));
+    for char in ['A', 'μ', '╝', 'Ω', '😀'] {
+        assert_eq!(
+            font.glyph_index(char as u32),
+            eager.glyph_index(char as u32)
+        );
+    }
+
+    let psf1 = Font::try_load_lazy(PSF1_FONT).unwrap();
+    assert_eq!(psf1.glyph_index('μ' as u32), eager.glyph_index('μ' as u32));
+
+    // The table is too big to parse, but lookups still work without it.
+    let mut small = Font::<16>::try_load_lazy_with_capacity(FONT).unwrap();
+    assert_eq!(
+        small.ensure_unicode_table(),
+        Err(LoadError::UnicodeTableFull)
+    );
+    assert_eq!(small.glyph_index('μ' as u32), eager.glyph_index('μ' as u32));
+}
+
+#[test]
+fn zero_capacity() {
+    let font = Font::<0>::try_load_with_capacity(FONT).unwrap();
+    let parsed = Font::load(FONT);
+
+    assert!(core::mem::size_of_val(&font) * 4 < core::mem::size_of_val(&parsed));
+    // Nothing big is stored inline, like the index cache, so it's just a few pointers and the header.
+    assert!(core::mem::size_of::<Font<0>>() <= 256);
+    for char in ['A', 'μ', '╝', 'Ω', '😀'] {
+        assert_eq!(
+            font.glyph_index(char as u32),
+            parsed.glyph_index(char as u32)
+        );
+    }
+    assert!(font.chars_for_glyph(0xe6).eq(parsed.chars_for_glyph(0xe6)));
+
+    let psf1 = Font::<0>::try_load_with_capacity(PSF1_FONT).unwrap();
+    assert_eq!(psf1.glyph_index('μ' as u32), parsed.glyph_index('μ' as u32));
+}
+
+#[test]
+fn missing_unicode_table() {
+    // A font that was loaded without a unicode table, but now says it has one.
+    let mut raw = FONT.to_vec();
+    raw[0xc] = 0;
+    let mut font = Font::load(&raw);
+    font.header.flags.unicode = true;
+
+    assert_eq!(font.glyph_index('μ' as u32), None);
+    assert_eq!(font.glyph_index('A' as u32), Some(0x41));
+    assert_eq!(font.display_glyph('μ', |_, _, _| ()), GlyphResult::Fallback);
+}
+
+#[test]
+fn static_font() {
+    const EMBEDDED: StaticFont = crate::embed!("../test.psfu");
+    const PSF1: StaticFont = crate::embed!("../test.psf");
+
+    for (embedded, raw) in [(EMBEDDED, FONT), (PSF1, PSF1_FONT)] {
+        let font = Font::load(raw);
+        assert_eq!(embedded.header, font.header);
+
+        for char in (0..0x3000).chain([0x1f600, u32::MAX]) {
+            assert_eq!(embedded.glyph_index(char), font.glyph_index(char));
+        }
+
+        for char in ['A', 'μ', '╝', '😀'] {
+            let (mut expected, mut actual) = (Vec::new(), Vec::new());
+            let result = font.display_glyph(char, |bit, x, y| expected.push((bit, x, y)));
+
+            assert_eq!(
+                embedded.display_glyph(char, |bit, x, y| actual.push((bit, x, y))),
+                result
+            );
+            assert_eq!(actual, expected);
+        }
+    }
+
+    assert_eq!(
+        EMBEDDED
+            .with_fallback('■')
+            .display_glyph('😀', |_, _, _| ()),
+        GlyphResult::Fallback
+    );
+    assert_eq!(StaticFont::entry_count(&FONT[..HEADER_SIZE + 256 * 16]), 0);
+}
+
+#[test]
+fn load_header_const() {
+    const HEADER: Header = Font::load_header_const(FONT);
+    const _: () = assert!(HEADER.glyph_width == 8 && HEADER.glyph_height == 16);
+
+    assert_eq!(HEADER, Font::load(FONT).header);
+    assert_eq!(
+        Font::load_header_const(PSF1_FONT),
+        Font::load(PSF1_FONT).header
+    );
+}
+
+#[test]
+fn header_magic() {
+    let psf2 = Font::load(FONT).header;
+    let psf1 = Font::load(PSF1_FONT).header;
+
+    assert!(psf2.is_psf2() && !psf2.is_psf1());
+    assert!(psf1.is_psf1() && !psf1.is_psf2());
+    assert_eq!(FONT[..4], PSF2_MAGIC);
+    assert_eq!(PSF1_FONT[..2], PSF1_MAGIC);
+
+    let header = Header {
+        magic: [0; 4],
+        ..psf2
+    };
+    assert!(!header.is_psf1() && !header.is_psf2());
+}
+
+#[test]
+fn psf1() {
+    let font = Font::load(PSF1_FONT);
+    let psf2 = Font::load(FONT);
+
+    assert_eq!(font.header.format, Version::Psf1);
+    assert_eq!(font.header.length, 256);
+    assert_eq!(font.header.glyph_width, 8);
+    assert_eq!(font.header.glyph_height, 16);
+    assert!(font.header.flags.unicode);
+
+    for char in ['A', 'µ', 'μ', '╝', 'Ω'] {
+        assert_eq!(font.glyph_index(char as u32), psf2.glyph_index(char as u32));
+    }
+
+    let mut rendered = [[0; 8]; 16];
+    font.display_glyph('&', |bit, x, y| rendered[y as usize][x as usize] |= bit);
+    psf2.display_glyph('&', |bit, x, y| rendered[y as usize][x as usize] ^= bit);
+    assert_eq!(rendered, [[0; 8]; 16]);
+}
+
+#[test]
+fn glyph_data() {
+    let font = Font::load(FONT);
+
+    assert_eq!(font.glyph_count(), 256);
+    assert_eq!(
+        font.glyph_data(0x41),
+        Some(&FONT[32 + 0x41 * 16..32 + 0x42 * 16])
+    );
+    assert_eq!(font.glyph_data(255).map(<[u8]>::len), Some(16));
+    assert_eq!(font.glyph_data(256), None);
+}
+
+#[test]
+fn display_glyph_trimmed() {
+    let font = Font::load(FONT);
+
+    let mut calls = 0;
+    let bounds = font.display_glyph_trimmed(' ', |_, _, _| calls += 1);
+    assert_eq!(bounds, Bounds::default());
+    assert_eq!(calls, 0);
+
+    let mut max = (0, 0);
+    let mut set = 0;
+    let bounds = font.display_glyph_trimmed('A', |bit, x, y| {
+        max = (max.0.max(x), max.1.max(y));
+        set += bit as u32;
+    });
+
+    assert!(bounds.w > 0 && bounds.w <= 8);
+    assert!(bounds.h > 0 && bounds.h < 16);
+    assert_eq!(max, (bounds.w as u8 - 1, bounds.h as u8 - 1));
+
+    let mut full = 0;
+    font.display_glyph('A', |bit, _, _| full += bit as u32);
+    assert_eq!(set, full);
+}
+
+#[test]
+fn glyph_bounds() {
+    let font = Font::load(FONT);
+
+    assert_eq!(font.glyph_bounds(' '), None);
+    assert_eq!(font.glyph_bounds('\u{10ffff}'), font.glyph_bounds('?'));
+
+    let bounds = font.glyph_bounds('|').unwrap();
+    font.display_glyph('|', |bit, x, y| {
+        assert!(bit == 0 || bounds.contains(x as u16, y as u16));
+    });
+    assert_eq!(bounds, font.display_glyph_trimmed('|', |_, _, _| ()));
+}
+
+#[test]
+fn display_str() {
+    let font = Font::load(FONT);
+
+    let mut glyph = [[0; 8]; 16];
+    font.display_glyph('µ', |bit, x, y| glyph[y as usize][x as usize] = bit);
+
+    let mut text = [[0; 16]; 32];
+    let end = font.display_str("Aµ\n µ", |bit, x, y| text[y as usize][x as usize] = bit);
+    assert_eq!(end, (16, 16));
+
+    for y in 0..16 {
+        assert_eq!(text[y][8..], glyph[y]);
+        assert_eq!(text[y + 16][8..], glyph[y]);
+    }
+}
+
+#[test]
+fn display_str_cursor() {
+    let font = Font::load(FONT);
+
+    let mut calls = 0;
+    assert_eq!(font.display_str("", |_, _, _| calls += 1), (0, 0));
+    assert_eq!(calls, 0);
+
+    assert_eq!(font.display_str("a\tb", |_, _, _| ()), (72, 0));
+    assert_eq!(font.display_str("\t\t", |_, _, _| ()), (128, 0));
+    assert_eq!(font.display_str("abc\n\nd", |_, _, _| ()), (8, 32));
+}
+
+#[test]
+fn display_str_long() {
+    let font = Font::load(FONT);
+
+    let mut max = (0, 0);
+    let end = font.display_str(&"A".repeat(9000), |_, x, y| {
+        max = (max.0.max(x), max.1.max(y));
+    });
+    assert_eq!(end, (u16::MAX, 0));
+    assert_eq!(max.0, u16::MAX);
+
+    let mut max = 0;
+    let end = font.display_str(&("\n".repeat(5000) + "A"), |_, _, y| max = max.max(y));
+    assert_eq!(end, (8, u16::MAX));
+    assert_eq!(max, u16::MAX);
+
+    assert_eq!(
+        font.display_str(&"\t".repeat(1100), |_, _, _| ()),
+        (u16::MAX, 0)
+    );
+    assert_eq!(
+        font.display_str_spaced("\t\t", i16::MAX, 0, |_, _, _| ()),
+        (u16::MAX, 0)
+    );
+}
+
+#[test]
+fn render_glyph_to() {
+    let font = Font::load(FONT);
+
+    let mut buf = [0xaa; 32];
+    assert_eq!(font.render_glyph_to('A', &mut buf, 2), Some(16));
+
+    for (y, row) in buf.chunks(2).enumerate() {
+        assert_eq!(row[0], font.glyph_data(0x41).unwrap()[y]);
+        assert_eq!(row[1], 0xaa);
+    }
+
+    assert_eq!(font.render_glyph_to('A', &mut buf, 3), None);
+    assert_eq!(font.render_glyph_to('A', &mut buf[..15], 1), None);
+    assert_eq!(font.render_glyph_to('A', &mut buf[..16], 1), Some(16));
+
+    // Strides that big can't fit, rather than overflowing.
+    assert_eq!(font.render_glyph_to('A', &mut buf, usize::MAX), None);
+    assert_eq!(
+        font.render_glyph_gray('A', &mut buf, usize::MAX, 255, 0),
+        None
+    );
+    assert_eq!(
+        font.render_glyph_scaled_smooth('A', 2, &mut buf, usize::MAX),
+        None
+    );
+
+    // A font without any glyphs still clears every row it says it wrote.
+    let empty = Font::empty();
+    let mut buf = [0xaa; 16];
+    assert_eq!(empty.render_glyph_to('A', &mut buf, 1), Some(16));
+    assert_eq!(buf, [0; 16]);
+
+    let mut buf = [0xaa; 8 * 16];
+    assert_eq!(empty.render_glyph_gray('A', &mut buf, 8, 255, 1), Some(16));
+    assert_eq!(buf, [1; 8 * 16]);
+    assert_eq!(
+        empty.render_glyph_scaled_smooth('A', 1, &mut buf, 8),
+        Some(16)
+    );
+    assert_eq!(buf, [0; 8 * 16]);
+}
+
+#[test]
+fn render_glyph_gray() {
+    let font = Font::load(FONT);
+
+    let mut buf = [1; 16 * 10];
+    assert_eq!(font.render_glyph_gray('A', &mut buf, 10, 255, 0), Some(16));
+
+    font.display_glyph('A', |bit, x, y| {
+        assert_eq!(buf[y as usize * 10 + x as usize], bit * 255);
+    });
+    assert!(buf.chunks(10).all(|row| row[8..] == [1, 1]));
+
+    assert_eq!(font.render_glyph_gray('A', &mut buf, 7, 255, 0), None);
+    assert_eq!(
+        font.render_glyph_gray('A', &mut buf[..127], 8, 255, 0),
+        None
+    );
+}
+
+#[test]
+fn glyph_buffer_len() {
+    let font = Font::load(FONT);
+
+    let mut bits = std::vec![0; font.glyph_buffer_len_1bpp()];
+    let stride = font.bytes_per_row() as usize;
+    assert_eq!(font.render_glyph_to('A', &mut bits, stride), Some(16));
+    assert_eq!(font.render_glyph_to('A', &mut bits[1..], stride), None);
+
+    let mut gray = std::vec![0; font.glyph_buffer_len_8bpp()];
+    assert_eq!(font.render_glyph_gray('A', &mut gray, 8, 1, 0), Some(16));
+    assert_eq!(font.render_glyph_gray('A', &mut gray[1..], 8, 1, 0), None);
+
+    assert_eq!(font.glyph_buffer_len_rgba(), 8 * 16);
+
+    // 9 pixel wide glyphs need 2 bytes for each row.
+    let glyphs = [0; 2 * 3];
+    let mut raw = [0; 64];
+    let len = FontBuilder::new(9, 3, &glyphs).write_to(&mut raw).unwrap();
+    let wide = Font::load(&raw[..len]);
+
+    assert_eq!(wide.glyph_buffer_len_1bpp(), 6);
+    assert_eq!(wide.glyph_buffer_len_8bpp(), 27);
+    assert_eq!(wide.glyph_buffer_len_rgba(), 27);
+}
+
+#[test]
+fn render_glyph_column_major() {
+    let font = Font::load(FONT);
+
+    let mut buf = [0xaa; 17];
+    assert_eq!(font.render_glyph_column_major('A', &mut buf), Some(16));
+    assert_eq!(buf[16], 0xaa);
+
+    font.display_glyph('A', |bit, x, y| {
+        assert_eq!(buf[(y / 8 * 8 + x) as usize] >> (y % 8) & 1, bit);
+    });
+
+    assert_eq!(font.render_glyph_column_major('A', &mut buf[..15]), None);
+
+    // A full 12x10 glyph, so the second band only has 2 rows in it.
+    let glyphs = [0xff, 0xf0].repeat(10);
+    let mut raw = [0; 64];
+    let len = FontBuilder::new(12, 10, &glyphs)
+        .write_to(&mut raw)
+        .unwrap();
+
+    let mut buf = [0xaa; 24];
+    let tall = Font::load(&raw[..len]);
+    assert_eq!(tall.render_glyph_column_major(0, &mut buf), Some(24));
+    assert_eq!(buf[..12], [0xff; 12]);
+    assert_eq!(buf[12..], [0x03; 12]);
+}
+
+#[test]
+fn render_glyph_scaled_smooth() {
+    let font = Font::load(FONT);
+
+    let mut plain = [0; 8 * 16];
+    let mut gray = [0; 8 * 16];
+    assert_eq!(
+        font.render_glyph_scaled_smooth('A', 1, &mut plain, 8),
+        Some(16)
+    );
+    font.render_glyph_gray('A', &mut gray, 8, 255, 0);
+    assert_eq!(plain, gray);
+
+    // The middle of every block is left alone, and only the borders are blended.
+    let mut smooth = std::vec![0; 24 * 48];
+    assert_eq!(
+        font.render_glyph_scaled_smooth('A', 3, &mut smooth, 24),
+        Some(48)
+    );
+    font.display_glyph('A', |bit, x, y| {
+        assert_eq!(
+            smooth[(y as usize * 3 + 1) * 24 + x as usize * 3 + 1],
+            bit * 255
+        );
+    });
+    assert!(smooth.iter().any(|value| *value != 0 && *value != 255));
+
+    assert_eq!(
+        font.render_glyph_scaled_smooth('A', 3, &mut smooth, 23),
+        None
+    );
+    assert_eq!(
+        font.render_glyph_scaled_smooth('A', 3, &mut smooth[1..], 24),
+        None
+    );
+
+    // One full row, so every edge of it blends into the clear space around it.
+    let mut raw = [0; 64];
+    let len = FontBuilder::new(8, 1, &[0xff]).write_to(&mut raw).unwrap();
+    let bar = Font::load(&raw[..len]);
+
+    let mut buf = [0; 16 * 2];
+    assert_eq!(bar.render_glyph_scaled_smooth(0, 2, &mut buf, 16), Some(2));
+
+    let mut row = [127; 16];
+    row[0] = 63;
+    row[15] = 63;
+    assert_eq!(buf[..16], row);
+    assert_eq!(buf[16..], row);
+}
+
+#[test]
+fn render_glyph_rgba() {
+    const FG: u32 = 0xffff_ffff;
+    const BG: u32 = 0xff00_0000;
+
+    let font = Font::load(FONT);
+
+    let mut buf = [0; 16 * 8];
+    font.render_glyph_rgba('A', &mut buf, 8, FG, BG);
+    font.display_glyph('A', |bit, x, y| {
+        assert_eq!(
+            buf[y as usize * 8 + x as usize],
+            if bit == 1 { FG } else { BG }
+        );
+    });
+
+    let mut clipped = [0; 4 * 3];
+    font.render_glyph_rgba('A', &mut clipped, 4, FG, BG);
+    for (i, pixel) in clipped.iter().enumerate() {
+        assert_eq!(*pixel, buf[i / 4 * 8 + i % 4]);
+    }
+}
+
+#[test]
+fn display_glyph_with() {
+    let font = Font::load(FONT);
+
+    let mut plain = [[0; 8]; 16];
+    let options = RenderOptions::default();
+    assert_eq!(
+        font.display_glyph_with('F', &options, |bit, x, y| {
+            plain[y as usize][x as usize] = bit;
+        }),
+        GlyphResult::Found
+    );
+
+    let mut flipped = [[0; 8]; 16];
+    font.display_glyph_transformed('F', Transform::Rotate180, |bit, x, y| {
+        flipped[y as usize][x as usize] = bit;
+    });
+
+    let mut glyph = [[0; 8]; 16];
+    font.display_glyph('F', |bit, x, y| glyph[y as usize][x as usize] = bit);
+    assert_eq!(plain, glyph);
+
+    // Flipped both ways and scaled up, which should be the same as scaling the rotated glyph.
+    let options = RenderOptions::new().scale(2).flip_x(true).flip_y(true);
+    let mut scaled = [[0; 16]; 32];
+    font.display_glyph_with('F', &options, |bit, x, y| {
+        scaled[y as usize][x as usize] = bit;
+    });
+    for (y, row) in scaled.iter().enumerate() {
+        for (x, bit) in row.iter().enumerate() {
+            assert_eq!(*bit, flipped[y / 2][x / 2]);
+        }
+    }
+
+    let options = RenderOptions::new().fallback('■');
+    let mut fallback = Vec::new();
+    assert_eq!(
+        font.display_glyph_with('😀', &options, |bit, x, y| fallback.push((bit, x, y))),
+        GlyphResult::Fallback
+    );
+
+    let mut expected = Vec::new();
+    font.display_glyph('■', |bit, x, y| expected.push((bit, x as u16, y as u16)));
+    assert_eq!(fallback, expected);
+    assert_eq!(font.fallback(), '?');
+}
+
+#[test]
+fn bit_order() {
+    let font = Font::load(FONT);
+
+    // For glyphs that are one byte wide, reading the bits backwards is a horizontal flip.
+    let options = RenderOptions::new().bit_order(BitOrder::LsbFirst);
+    let mut reversed = [[0; 8]; 16];
+    font.display_glyph_with('F', &options, |bit, x, y| {
+        reversed[y as usize][x as usize] = bit;
+    });
+
+    let mut flipped = [[0; 8]; 16];
+    font.display_glyph_transformed('F', Transform::FlipH, |bit, x, y| {
+        flipped[y as usize][x as usize] = bit;
+    });
+    assert_eq!(reversed, flipped);
+
+    // A 12x1 glyph, where the padding is in the high bits of the last byte.
+    let mut raw = [0; 64];
+    let len = FontBuilder::new(12, 1, &[0b0000_0001, 0b0000_1000])
+        .write_to(&mut raw)
+        .unwrap();
+    let wide = Font::load(&raw[..len]);
+
+    let mut set = Vec::new();
+    wide.display_glyph_with(0, &options, |bit, x, _| {
+        if bit == 1 {
+            set.push(x);
+        }
+    });
+    assert_eq!(set, [0, 11]);
+
+    set.clear();
+    wide.display_glyph_with(0, &RenderOptions::new(), |bit, x, _| {
+        if bit == 1 {
+            set.push(x);
+        }
+    });
+    assert_eq!(set, [7]);
+}
+
+#[test]
+fn display_glyph_rotated() {
+    // Two 3x2 glyphs, so the rotated glyphs are 2x3.
+    let glyphs = [
+        0b1100_0000,
+        0b0010_0000, //
+        0,
+        0,
+    ];
+    let mut buf = [0; 64];
+    let len = FontBuilder::new(3, 2, &glyphs).write_to(&mut buf).unwrap();
+    let font = Font::load(&buf[..len]);
+
+    for (rotation, expected) in [
+        (Rotation::R90, [[0, 1], [0, 1], [1, 0]]),
+        (Rotation::R270, [[0, 1], [1, 0], [1, 0]]),
+    ] {
+        let mut rotated = [[0; 2]; 3];
+        let size = font.display_glyph_rotated(0, rotation, |bit, x, y| {
+            rotated[y as usize][x as usize] = bit;
+        });
+
+        assert_eq!(size, (2, 3));
+        assert_eq!(rotated, expected);
+    }
+}
+
+#[test]
+fn display_glyph_transformed() {
+    let font = Font::load(FONT);
+
+    let mut glyph = [[0; 8]; 16];
+    font.display_glyph('F', |bit, x, y| glyph[y as usize][x as usize] = bit);
+
+    for transform in [
+        Transform::None,
+        Transform::FlipH,
+        Transform::FlipV,
+        Transform::Rotate180,
+    ] {
+        let mut flipped = [[0; 8]; 16];
+        font.display_glyph_transformed('F', transform, |bit, x, y| {
+            flipped[y as usize][x as usize] = bit;
+        });
+
+        for (y, row) in glyph.iter().enumerate() {
+            for (x, bit) in row.iter().enumerate() {
+                let (tx, ty) = match transform {
+                    Transform::None => (x, y),
+                    Transform::FlipH => (7 - x, y),
+                    Transform::FlipV => (x, 15 - y),
+                    Transform::Rotate180 => (7 - x, 15 - y),
+                };
+
+                assert_eq!(flipped[ty][tx], *bit);
+            }
+        }
+    }
+
+    assert_eq!(
+        font.display_glyph_transformed('F', Transform::FlipH, |_, _, _| ()),
+        GlyphResult::Found
+    );
+    assert_eq!(
+        font.display_glyph_transformed('😀', Transform::FlipH, |_, _, _| ()),
+        GlyphResult::Fallback
+    );
+}
+
+#[test]
+fn display_glyph_scaled() {
+    let font = Font::load(FONT);
+
+    let mut glyph = [[0; 8]; 16];
+    font.display_glyph('A', |bit, x, y| glyph[y as usize][x as usize] = bit);
+
+    let mut scaled = [[0; 24]; 48];
+    let mut calls = 0;
+    font.display_glyph_scaled('A', 3, |bit, x, y| {
+        scaled[y as usize][x as usize] = bit;
+        calls += 1;
+    });
+
+    assert_eq!(calls, 8 * 16 * 9);
+    for (y, row) in scaled.iter().enumerate() {
+        for (x, bit) in row.iter().enumerate() {
+            assert_eq!(*bit, glyph[y / 3][x / 3]);
+        }
+    }
+
+    let mut unscaled = [[0; 8]; 16];
+    font.display_glyph_scaled('A', 0, |bit, x, y| unscaled[y as usize][x as usize] = bit);
+    assert_eq!(unscaled, glyph);
+
+    assert_eq!(
+        font.display_glyph_scaled('A', 2, |_, _, _| ()),
+        GlyphResult::Found
+    );
+    assert_eq!(
+        font.display_glyph_scaled('😀', 2, |_, _, _| ()),
+        GlyphResult::Fallback
+    );
+}
+
+#[test]
+fn glyph_pixels() {
+    let font = Font::load(FONT);
+
+    let mut pixels = font.glyph_pixels('A');
+    font.display_glyph('A', |bit, x, y| {
+        if bit == 1 {
+            assert_eq!(pixels.next(), Some((x, y)));
+        }
+    });
+    assert_eq!(pixels.next(), None);
+
+    assert_eq!(font.glyph_pixels(' ').count(), 0);
+    assert!(font.glyph_pixels('\u{10ffff}').eq(font.glyph_pixels('?')));
+}
+
+#[test]
+fn glyph_rows() {
+    let font = Font::load(FONT);
+
+    let mut rows = [0; 16];
+    font.display_glyph('A', |bit, x, y| {
+        rows[y as usize] |= (bit as u32) << (31 - x);
+    });
+
+    assert!(font.glyph_rows('A').eq(rows));
+    assert!(font.glyph_rows(' ').all(|row| row == 0));
+    assert_eq!(font.glyph_rows('A').count(), 16);
+}
+
+#[test]
+fn fallback() {
+    let mut font = Font::load(FONT);
+    assert_eq!(font.fallback(), '?');
+
+    font.set_fallback('■');
+    assert!(font.glyph_pixels('\u{10ffff}').eq(font.glyph_pixels('■')));
+
+    font.set_fallback('\u{10fffe}');
+    assert!(font.glyph_rows('\u{10ffff}').eq(font
+        .glyph_data(0)
+        .unwrap()
+        .iter()
+        .map(|row| (*row as u32) << 24)));
+}
+
+#[test]
+fn glyph_result() {
+    let mut font = Font::load(FONT);
+
+    assert_eq!(font.display_glyph('A', |_, _, _| ()), GlyphResult::Found);
+    assert_eq!(font.display_glyph('μ', |_, _, _| ()), GlyphResult::Found);
+    assert_eq!(
+        font.display_glyph('\u{10ffff}', |_, _, _| ()),
+        GlyphResult::Fallback
+    );
+
+    font.set_fallback('\u{10fffe}');
+    assert_eq!(
+        font.display_glyph('\u{10ffff}', |_, _, _| ()),
+        GlyphResult::Missing
+    );
+}
+
+#[test]
+fn has_glyph() {
+    let font = Font::load(FONT);
+
+    assert!(font.has_glyph('A'));
+    assert!(font.has_glyph('\0'));
+    assert!(font.has_glyph('╝'));
+    assert!(font.has_glyph('\u{2126}'));
+    assert!(!font.has_glyph('\u{10ffff}'));
+    assert!(!font.has_glyph('あ'));
+}
+
+#[test]
+fn no_unicode_table() {
+    let mut raw = [0; 32 + 256 * 16 + 4];
+    raw[..32 + 256 * 16].copy_from_slice(&FONT[..32 + 256 * 16]);
+    raw[0xc] = 0;
+    raw[32 + 256 * 16..].copy_from_slice(&[0xc2, 0xb5, 0xff, 0xff]);
+
+    let font = Font::load(&raw);
+    assert!(!font.header.flags.unicode);
+    assert!(font.unicode.is_none());
+    assert_eq!(font.glyph_index('µ' as u32), Some(0xb5));
+}
+
+#[test]
+fn unicode_table_full() {
+    const GLYPHS: usize = 32 + 256 * 16;
+
+    let mut raw = [0xff; GLYPHS + 1100 * 2];
+    raw[..GLYPHS].copy_from_slice(&FONT[..GLYPHS]);
+    for (i, entry) in raw[GLYPHS..].chunks_mut(2).enumerate() {
+        // Two byte UTF-8 lead bytes, so each one is its own character.
+        entry[0] = 0xc2 + (i / 64) as u8;
+        entry[1] = 0x80 + (i % 64) as u8;
+    }
+
+    assert_eq!(
+        Font::try_load(&raw).unwrap_err(),
+        LoadError::UnicodeTableFull
+    );
+}
+
+#[test]
+fn sequences() {
+    const GLYPHS: usize = 32 + 256 * 16;
+    const TABLE: &[u8] = b"A\xfeA\xcc\x81\xfe\xc3\x81\xffB\xff\xfeC\xcc\xa7\xff";
+
+    let mut raw = [0; GLYPHS + TABLE.len()];
+    raw[..GLYPHS].copy_from_slice(&FONT[..GLYPHS]);
+    raw[GLYPHS..].copy_from_slice(TABLE);
+
+    let font = Font::load(&raw);
+
+    assert_eq!(font.glyph_index('A' as u32), Some(0x41));
+    assert_eq!(font.glyph_index('Á' as u32), None);
+    assert_eq!(font.sequence_index(&['A', '\u{301}']), Some(0));
+    assert_eq!(font.sequence_index(&['Á']), Some(0));
+    assert_eq!(font.sequence_index(&['C', '\u{327}']), Some(2));
+    assert_eq!(font.sequence_index(&['C']), None);
+    assert_eq!(font.sequence_index(&['B']), None);
+    assert_eq!(Font::load(FONT).sequence_index(&['A', '\u{301}']), None);
+}
+
+#[test]
+fn psf1_sequences() {
+    const GLYPHS: usize = 4 + 256 * 16;
+    const TABLE: [u16; 9] = [
+        0x41, 0xfffe, 0x41, 0x301, 0xffff, 0xfffe, 0x43, 0x327, 0xffff,
+    ];
+
+    let mut raw = [0; GLYPHS + TABLE.len() * 2];
+    raw[..GLYPHS].copy_from_slice(&PSF1_FONT[..GLYPHS]);
+    for (i, value) in TABLE.iter().enumerate() {
+        raw[GLYPHS + i * 2..GLYPHS + i * 2 + 2].copy_from_slice(&value.to_le_bytes());
+    }
+
+    let font = Font::load(&raw);
+
+    assert_eq!(font.sequence_index(&['A', '\u{301}']), Some(0));
+    assert_eq!(font.sequence_index(&['C', '\u{327}']), Some(1));
+    assert_eq!(font.sequence_index(&['A']), None);
+    assert_eq!(font.sequence_index(&['C', '\u{327}', 'C']), None);
+}
+
+#[test]
+fn stray_continuation_bytes() {
+    const GLYPHS: usize = 32 + 256 * 16;
+    const TABLE: &[u8] = b"\xb5\xc2\xb5\xff\x80\x80\xce\xbc\xff";
+
+    let mut raw = [0; GLYPHS + TABLE.len()];
+    raw[..GLYPHS].copy_from_slice(&FONT[..GLYPHS]);
+    raw[GLYPHS..].copy_from_slice(TABLE);
+
+    let font = Font::load(&raw);
+
+    assert_eq!(font.glyph_index('µ' as u32), Some(0));
+    assert_eq!(font.glyph_index('μ' as u32), Some(1));
+    assert_eq!(font.unicode.as_ref().unwrap().len(), 2);
+}
+
+#[test]
+fn truncated_unicode_entry() {
+    const GLYPHS: usize = 32 + 256 * 16;
+    const TABLE: &[u8] = b"\xc2\xb5\xe2\x95\xff\xce\xbc\xf0\x9f";
+
+    let mut raw = [0; GLYPHS + TABLE.len()];
+    raw[..GLYPHS].copy_from_slice(&FONT[..GLYPHS]);
+    raw[GLYPHS..].copy_from_slice(TABLE);
+
+    let font = Font::load(&raw);
+
+    assert_eq!(font.glyph_index('µ' as u32), Some(0));
+    assert_eq!(font.glyph_index('μ' as u32), Some(1));
+    assert_eq!(font.unicode.as_ref().unwrap().len(), 2);
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn load_gz() {
+    const GZIP_FONT: &[u8] = include_bytes!("../test.psfu.gz");
+
+    let mut scratch = [0; 8192];
+    let font = Font::load_gz(GZIP_FONT, &mut scratch).unwrap();
+    let plain = Font::load(FONT);
+
+    assert_eq!(font.data, plain.data);
+    assert_eq!(font.glyph_index('μ' as u32), Some(0xe6));
+
+    let mut small = [0; 4096];
+    assert_eq!(
+        Font::load_gz(GZIP_FONT, &mut small).unwrap_err(),
+        LoadError::ScratchTooSmall
+    );
+
+    let mut corrupted = [0; GZIP_FONT.len()];
+    corrupted.copy_from_slice(GZIP_FONT);
+    corrupted[GZIP_FONT.len() / 2] ^= 0x55;
+    assert!(Font::load_gz(&corrupted, &mut scratch).is_err());
+
+    assert_eq!(
+        Font::load_gz(FONT, &mut scratch).unwrap_err(),
+        LoadError::BadGzip
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn into_owned() {
+    let raw = alloc::vec::Vec::from(FONT);
+    let font = Font::load(&raw).into_owned();
+    drop(raw);
+
+    assert_eq!(font.glyph_index('μ' as u32), Some(0xe6));
+    assert_eq!(font.glyph_data(0x41), Font::load(FONT).glyph_data(0x41));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn set_glyph() {
+    use crate::EditError;
+
+    let mut font = Font::load(FONT).into_owned();
+    let bitmap = [0xaa; 16];
+
+    font.set_glyph(0x41, &bitmap).unwrap();
+    assert_eq!(font.glyph_data(0x41), Some(&bitmap[..]));
+    assert_eq!(font.glyph_data(0x42), Font::load(FONT).glyph_data(0x42));
+
+    assert_eq!(
+        font.set_glyph(256, &bitmap),
+        Err(EditError::IndexOutOfBounds)
+    );
+    assert_eq!(
+        font.set_glyph(0x41, &bitmap[..8]),
+        Err(EditError::WrongLength {
+            expected: 16,
+            actual: 8
+        })
+    );
+
+    let saved = font.to_bytes().unwrap();
+    assert_eq!(Font::load(&saved).glyph_data(0x41), Some(&bitmap[..]));
+}
+
+#[test]
+fn clone() {
+    let mut font = Font::load(FONT);
+    font.set_fallback('■');
+
+    let copy = font.clone();
+    font.set_fallback('?');
+
+    assert_eq!(copy.fallback(), '■');
+    assert_eq!(copy.glyph_index('μ' as u32), font.glyph_index('μ' as u32));
+    assert_eq!(copy.glyph_data(0x41), font.glyph_data(0x41));
+}
+
+#[test]
+fn font_builder() {
+    // Three 12x2 glyphs, which need two bytes for each row.
+    let glyphs = [
+        0x00, 0x00, 0x00, 0x00, //
+        0xff, 0xf0, 0x80, 0x10, //
+        0xaa, 0xa0, 0x55, 0x50,
+    ];
+    let unicode = [('a', 1), ('╝', 2), ('μ', 2)];
+    let sequences: [(&[char], usize); 1] = [(&['e', '\u{301}'], 0)];
+
+    let builder = FontBuilder::new(12, 2, &glyphs)
+        .unicode(&unicode)
+        .sequences(&sequences);
+
+    let mut buf = [0; 128];
+    assert_eq!(
+        builder.write_to(&mut buf[..16]),
+        Err(WriteError::BufferTooSmall(builder.byte_len()))
+    );
+
+    let len = builder.write_to(&mut buf).unwrap();
+    assert_eq!(len, builder.byte_len());
+
+    let font = Font::load(&buf[..len]);
+    assert_eq!(font.header.length, 3);
+    assert_eq!(font.header.glyph_size, 4);
+    assert_eq!(font.header.glyph_width, 12);
+    assert_eq!(font.header.glyph_height, 2);
+    assert!(font.header.flags.unicode);
+    assert_eq!(font.glyph_data(1), Some(&glyphs[4..8]));
+    assert_eq!(font.glyph_index('╝' as u32), Some(2));
+    assert_eq!(font.glyph_index('μ' as u32), Some(2));
+    assert_eq!(font.sequence_index(&['e', '\u{301}']), Some(0));
+
+    let plain = FontBuilder::new(12, 2, &glyphs);
+    let len = plain.write_to(&mut buf).unwrap();
+    assert_eq!(len, 32 + glyphs.len());
+    assert!(!Font::load(&buf[..len]).header.flags.unicode);
+
+    assert_eq!(
+        FontBuilder::new(12, 2, &glyphs[..6]).write_to(&mut buf),
+        Err(WriteError::InvalidGlyphData)
+    );
+    assert_eq!(
+        FontBuilder::new(12, 2, &glyphs)
+            .unicode(&[('a', 3)])
+            .write_to(&mut buf),
+        Err(WriteError::InvalidMapping)
+    );
+    assert_eq!(
+        FontBuilder::new(MAX_GLYPH_DIMENSION + 8, 1, &[0; 33]).write_to(&mut buf),
+        Err(WriteError::InvalidDimensions)
+    );
+    assert_eq!(
+        FontBuilder::new(u32::MAX, u32::MAX, &glyphs).write_to(&mut buf),
+        Err(WriteError::InvalidDimensions)
+    );
+}
+
+#[test]
+fn header_endianness() {
+    for value in [0, 1, 0x1234_5678, u32::MAX] {
+        assert_eq!(crate::as_u32_le(crate::as_u32_le_bytes(value)), value);
+    }
+
+    // Spelled out byte by byte, so this doesn't depend on what the host is.
+    assert_eq!(
+        crate::as_u32_le_bytes(0x1234_5678),
+        [0x78, 0x56, 0x34, 0x12]
+    );
+
+    let glyphs = [0; 2 * 0x102];
+    let mut buf = [0; 32 + 2 * 0x102];
+
+    let builder = FontBuilder::new(9, 1, &glyphs);
+    let len = builder.write_to(&mut buf).unwrap();
+
+    assert_eq!(buf[0x4..0x8], [0, 0, 0, 0]);
+    assert_eq!(buf[0x8..0xc], [32, 0, 0, 0]);
+    assert_eq!(buf[0x10..0x14], [0x02, 0x01, 0, 0]);
+    assert_eq!(buf[0x14..0x18], [2, 0, 0, 0]);
+    assert_eq!(buf[0x1c..0x20], [9, 0, 0, 0]);
+
+    let font = Font::load(&buf[..len]);
+    assert_eq!(font.header.length, 0x102);
+    assert_eq!(font.header.glyph_width, 9);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn round_trip() {
+    for raw in [FONT, PSF1_FONT] {
+        let font = Font::load(raw);
+        let bytes = font.to_bytes().unwrap();
+        let reloaded = Font::load(&bytes);
+
+        assert_eq!(reloaded.header.format, Version::Psf2);
+        assert_eq!(reloaded.header.length, font.header.length);
+        assert_eq!(reloaded.header.glyph_size, font.header.glyph_size);
+        assert_eq!(reloaded.header.glyph_width, font.header.glyph_width);
+        assert_eq!(reloaded.header.glyph_height, font.header.glyph_height);
+        assert_eq!(reloaded.header.flags.unicode, font.header.flags.unicode);
+
+        for index in 0..font.glyph_count() {
+            assert_eq!(reloaded.glyph_data(index), font.glyph_data(index));
+        }
+
+        for (char, index) in font.unicode_entries() {
+            assert_eq!(reloaded.glyph_index(char as u32), Some(index));
+        }
+
+        assert_eq!(reloaded.to_bytes().unwrap(), bytes);
+    }
+
+    let glyphs = [0; 4 * 16];
+    let sequences: [(&[char], usize); 2] = [(&['e', '\u{301}'], 1), (&['a', '\u{301}'], 3)];
+    let bytes = FontBuilder::new(8, 16, &glyphs)
+        .unicode(&[('é', 1)])
+        .sequences(&sequences)
+        .build()
+        .unwrap();
+
+    let reloaded = Font::load(&bytes);
+    assert_eq!(reloaded.to_bytes().unwrap(), bytes);
+    assert_eq!(reloaded.sequence_index(&['a', '\u{301}']), Some(3));
+}
+
+#[test]
+fn unicode_entries() {
+    let font = Font::load(FONT);
+
+    assert_eq!(
+        font.unicode_entries().count(),
+        font.unicode.as_ref().unwrap().len()
+    );
+    assert!(font.unicode_entries().any(|entry| entry == ('µ', 0xe6)));
+    assert!(font.unicode_entries().any(|entry| entry == ('A', 0x41)));
+    for (char, index) in font.unicode_entries().filter(|(char, _)| !char.is_ascii()) {
+        assert_eq!(font.glyph_index(char as u32), Some(index));
+    }
+
+    let glyphs = [0; 16];
+    let mut buf = [0; 64];
+    let len = FontBuilder::new(8, 16, &glyphs).write_to(&mut buf).unwrap();
+    assert_eq!(Font::load(&buf[..len]).unicode_entries().count(), 0);
+}
+
+#[test]
+fn chars_for_glyph() {
+    let font = Font::load(FONT);
+
+    let mut chars = font.chars_for_glyph(0xe6);
+    assert_eq!(chars.next(), Some('µ'));
+    assert_eq!(chars.next(), Some('μ'));
+    assert_eq!(chars.next(), None);
+
+    assert!(font.chars_for_glyph(0x41).eq(['A']));
+    assert!(font
+        .chars_for_glyph(0xea)
+        .all(|char| font.glyph_index(char as u32) == Some(0xea)));
+    assert_eq!(font.chars_for_glyph(0xea).count(), 2);
+    assert_eq!(font.chars_for_glyph(1000).count(), 0);
+}
+
+#[test]
+fn is_valid_index() {
+    let font = Font::load(FONT);
+
+    assert!(font.is_valid_index(0));
+    assert!(font.is_valid_index(255));
+    assert!(!font.is_valid_index(256));
+    assert!(!font.is_valid_index(usize::MAX));
+}
+
+#[test]
+fn primary_char_for() {
+    let font = Font::load(FONT);
+
+    assert_eq!(font.primary_char_for(0x41), Some('A'));
+    assert_eq!(font.primary_char_for(0xe6), Some('µ'));
+    assert_eq!(font.primary_char_for(256), None);
+
+    let glyphs = [0; 130];
+    let mut buf = [0; 512];
+    let len = FontBuilder::new(8, 1, &glyphs)
+        .unicode(&[('ŝ', 129), ('ĉ', 129)])
+        .write_to(&mut buf)
+        .unwrap();
+    let font = Font::load(&buf[..len]);
+
+    assert_eq!(font.primary_char_for(129), Some('ĉ'));
+    assert_eq!(font.primary_char_for(128), None);
+    assert_eq!(font.primary_char_for(2), Some('\u{2}'));
+}
+
+#[test]
+fn glyph_indices() {
+    let glyphs = [0; 3];
+    let mut buf = [0; 128];
+    let len = FontBuilder::new(8, 1, &glyphs)
+        .unicode(&[('ŝ', 0), ('ĉ', 1), ('ŝ', 2)])
+        .write_to(&mut buf)
+        .unwrap();
+    let font = Font::load(&buf[..len]);
+
+    assert!(font.glyph_indices('ŝ').eq([0, 2]));
+    assert_eq!(font.glyph_index('ŝ' as u32), Some(2));
+    assert!(font.glyph_indices('ĉ').eq([1]));
+    assert_eq!(font.glyph_indices('ŵ').count(), 0);
+
+    let psf2 = Font::load(FONT);
+    let psf1 = Font::load(PSF1_FONT);
+    for char in ['A', 'μ', '╝', 'Ω'] {
+        assert!(psf1.glyph_indices(char).eq(psf2.glyph_indices(char)));
+        assert_eq!(
+            psf2.glyph_indices(char).last(),
+            psf2.glyph_index(char as u32)
+        );
+    }
+
+    let mut raw = FONT.to_vec();
+    raw[0xc] = 0;
+    assert!(Font::load(&raw).glyph_indices('A').eq([0x41]));
+    assert_eq!(Font::load(&raw).glyph_indices('μ').count(), 0);
+}
+
+#[test]
+fn lookup_kind() {
+    let glyphs = [0; 2];
+    let sequences: [(&[char], usize); 1] = [(&['ĉ', '\u{301}'], 1)];
+    let mut buf = [0; 128];
+    let len = FontBuilder::new(8, 1, &glyphs)
+        .unicode(&[('ŝ', 0)])
+        .sequences(&sequences)
+        .write_to(&mut buf)
+        .unwrap();
+    let font = Font::load(&buf[..len]);
+
+    assert_eq!(font.lookup_kind('ŝ'), Lookup::Single(0));
+    assert_eq!(font.lookup_kind('ĉ'), Lookup::SequenceOnly);
+    assert_eq!(font.lookup_kind('\u{301}'), Lookup::SequenceOnly);
+    assert_eq!(font.lookup_kind('ŵ'), Lookup::Absent);
+    assert_eq!(font.lookup_kind('a'), Lookup::Absent);
+
+    assert_eq!(Font::load(FONT).lookup_kind('a'), Lookup::Single(0x61));
+}
+
+#[test]
+fn font_stack() {
+    // A single 4x4 glyph for a character the main font doesn't have.
+    let glyphs = [0xf0, 0x90, 0x90, 0xf0];
+    let mut buf = [0; 64];
+    let len = FontBuilder::new(4, 4, &glyphs)
+        .unicode(&[('⚡', 0)])
+        .write_to(&mut buf)
+        .unwrap();
+
+    let fonts = std::vec![Font::load(&buf[..len]), Font::load(FONT)];
+    let stack = FontStack::new(&fonts);
+
+    let mut pixels = Vec::new();
+    assert_eq!(
+        stack.display_glyph('⚡', |bit, x, y| pixels.push((bit, x, y))),
+        (4, 4)
+    );
+    assert_eq!(pixels.len(), 16);
+    assert_eq!(stack.font_for('⚡').unwrap().width(), 4);
+
+    assert_eq!(stack.display_glyph('A', |_, _, _| ()), (8, 16));
+    assert_eq!(stack.font_for('A').unwrap().width(), 8);
+
+    // Nothing has it, so the first font's fallback is used.
+    assert_eq!(stack.font_for('😀').unwrap().width(), 4);
+    assert_eq!(
+        FontStack::<16>::new(&[]).display_glyph('A', |_, _, _| ()),
+        (0, 0)
+    );
+}
+
+#[test]
+fn glyphs() {
+    let font = Font::load(FONT);
+
+    assert_eq!(font.glyphs().count(), 256);
+    for (index, data) in font.glyphs() {
+        assert_eq!(Some(data), font.glyph_data(index as u32));
+    }
+
+    let glyphs = [1, 2, 3];
+    let mut buf = [0; 64];
+    let len = FontBuilder::new(8, 1, &glyphs).write_to(&mut buf).unwrap();
+    assert!(Font::load(&buf[..len])
+        .glyphs()
+        .eq([(0, &[1][..]), (1, &[2][..]), (2, &[3][..])]));
+}
+
+#[test]
+fn glyph_rows_array() {
+    let font = Font::load(FONT);
+    let rows = font.glyph_u8x16('A').unwrap();
+
+    assert_eq!(&rows[..], font.glyph_bytes('A').unwrap());
+    assert_eq!(font.glyph_rows_array::<16, _>('A'), Some(rows));
+    assert_eq!(font.glyph_rows_array::<8, _>('A'), None);
+
+    // 5x2 glyphs, with padding bits that are set.
+    let glyphs = [0xff, 0x0f];
+    let mut buf = [0; 64];
+    let len = FontBuilder::new(5, 2, &glyphs).write_to(&mut buf).unwrap();
+    let font = Font::load(&buf[..len]);
+
+    assert_eq!(font.glyph_rows_array::<2, _>(0), Some([0xf8, 0x08]));
+    assert_eq!(font.glyph_u8x16(0), None);
+}
+
+#[test]
+fn glyph_row_bytes() {
+    let font = Font::load(FONT);
+    let data = font.glyph_bytes('A').unwrap();
+
+    assert_eq!(font.row_padding_bits(), 0);
+    for row in 0..16 {
+        assert_eq!(
+            font.glyph_row_bytes('A', row),
+            Some(&data[row as usize..=row as usize])
+        );
+    }
+    assert_eq!(font.glyph_row_bytes('A', 16), None);
+
+    // Two 12x2 glyphs, which need two bytes for each row.
+    let glyphs = [0xff, 0xf0, 0x80, 0x10, 0, 0, 0, 0];
+    let mut buf = [0; 64];
+    let len = FontBuilder::new(12, 2, &glyphs).write_to(&mut buf).unwrap();
+    let font = Font::load(&buf[..len]);
+
+    assert_eq!(font.row_padding_bits(), 4);
+    assert_eq!(font.glyph_row_bytes('\0', 1), Some(&[0x80, 0x10][..]));
+    assert_eq!(font.glyph_row_bytes('\x02', 0), None);
+}
+
+#[test]
+fn duplicate_glyphs() {
+    let font = Font::load(FONT);
+
+    assert!(font.glyph_eq(0x41, 0x41));
+    assert!(!font.glyph_eq(0x41, 0x42));
+    assert!(!font.glyph_eq(0x41, 256));
+
+    for (a, b) in font.duplicate_glyphs() {
+        assert!(a < b);
+        assert_eq!(font.glyph_data(a as u32), font.glyph_data(b as u32));
+    }
+
+    let glyphs = [1, 2, 1, 3, 2, 1];
+    let mut buf = [0; 64];
+    let len = FontBuilder::new(8, 1, &glyphs).write_to(&mut buf).unwrap();
+    assert!(Font::load(&buf[..len])
+        .duplicate_glyphs()
+        .eq([(0, 2), (0, 5), (1, 4), (2, 5)]));
+}
+
+#[test]
+fn glyph_weight() {
+    let font = Font::load(FONT);
+
+    let mut set = 0;
+    font.display_glyph('A', |bit, _, _| set += bit as u32);
+
+    assert_eq!(font.glyph_weight('A'), set);
+    assert_eq!(font.glyph_weight(' '), 0);
+    assert_eq!(font.glyph_weight('█'), 8 * 16);
+
+    // The padding bits in these glyphs are set, but shouldn't count.
+    let glyphs = [0xff, 0xff, 0xff, 0xff];
+    let mut buf = [0; 64];
+    let len = FontBuilder::new(12, 2, &glyphs).write_to(&mut buf).unwrap();
+    assert_eq!(Font::load(&buf[..len]).glyph_weight(0), 24);
+}
+
+#[test]
+fn is_blank() {
+    let font = Font::load(FONT);
+
+    assert!(font.is_blank(' '));
+    assert!(font.is_blank(0));
+    assert!(!font.is_blank('A'));
+    assert!(!font.is_blank('.'));
+
+    let glyphs = [0x00, 0x0f, 0x00, 0x10];
+    let mut buf = [0; 64];
+    let len = FontBuilder::new(12, 1, &glyphs).write_to(&mut buf).unwrap();
+    let font = Font::load(&buf[..len]);
+    assert!(font.is_blank(0));
+    assert!(!font.is_blank(1));
+}
+
+#[test]
+fn non_byte_aligned_width() {
+    // Two 9x2 glyphs, with all of the padding bits set.
+    let glyphs = [0xff, 0xff, 0x00, 0x7f, 0x80, 0x7f, 0x7f, 0xff];
+    let mut buf = [0; 64];
+    let len = FontBuilder::new(9, 2, &glyphs).write_to(&mut buf).unwrap();
+    let font = Font::load(&buf[..len]);
+
+    for (index, expected) in [
+        [[1, 1, 1, 1, 1, 1, 1, 1, 1], [0, 0, 0, 0, 0, 0, 0, 0, 0]],
+        [[1, 0, 0, 0, 0, 0, 0, 0, 0], [0, 1, 1, 1, 1, 1, 1, 1, 1]],
+    ]
+    .iter()
+    .enumerate()
+    {
+        let mut rendered = [[2; 9]; 2];
+        let mut calls = 0;
+        font.display_glyph(index as u32, |bit, x, y| {
+            rendered[y as usize][x as usize] = bit;
+            calls += 1;
+        });
+
+        assert_eq!(calls, 9 * 2);
+        assert_eq!(&rendered, expected);
+    }
+}
+
+#[test]
+fn display_glyph_max_x() {
+    let font = Font::load(FONT);
+
+    let mut max = (0, 0);
+    font.display_glyph('A', |_, x, y| max = (max.0.max(x), max.1.max(y)));
+
+    assert_eq!(max.0 as u32, font.header.glyph_width - 1);
+    assert_eq!(max.1 as u32, font.header.glyph_height - 1);
+}
+
+#[test]
+fn dimensions() {
+    let font = Font::load(FONT);
+
+    assert_eq!(font.width(), 8);
+    assert_eq!(font.height(), 16);
+    assert_eq!(font.bytes_per_row(), 1);
+
+    let glyphs = [0; 3 * 5];
+    let mut buf = [0; 64];
+    let len = FontBuilder::new(17, 5, &glyphs).write_to(&mut buf).unwrap();
+    let font = Font::load(&buf[..len]);
+
+    assert_eq!(font.width(), 17);
+    assert_eq!(font.height(), 5);
+    assert_eq!(font.bytes_per_row(), 3);
+    assert_eq!(font.header.glyph_size, 15);
+}
+
+#[test]
+fn error_display() {
+    assert_eq!(
+        Font::try_load(&FONT[..1024]).unwrap_err().to_string(),
+        "font data truncated: expected at least 4128 bytes, got 1024"
+    );
+    assert_eq!(
+        LoadError::UnsupportedVersion(3).to_string(),
+        "unsupported psf2 version 3"
+    );
+    assert_eq!(
+        LoadError::InvalidHeaderSize(16).to_string(),
+        "header size of 16 bytes is too small for psf2"
+    );
+    assert_eq!(
+        WriteError::BufferTooSmall(64).to_string(),
+        "buffer is too small: the font needs 64 bytes"
+    );
+    assert_eq!(
+        WriteError::InvalidDimensions.to_string(),
+        "glyphs are too big to be displayed"
+    );
+
+    let error: &dyn core::error::Error = &LoadError::BadMagic;
+    assert!(error.source().is_none());
+}
+
+#[test]
+fn glyph_size() {
+    const SIZE: Option<u32> = glyph_size_for(8, 16);
+
+    assert_eq!(SIZE, Some(16));
+    assert_eq!(glyph_size_for(9, 16), Some(32));
+    assert_eq!(glyph_size_for(0, 16), Some(0));
+    assert_eq!(glyph_size_for(u32::MAX, u32::MAX), None);
+    assert_eq!(glyph_size_for(u32::MAX, 7), Some(0x2000_0000 * 7));
+    assert_eq!(glyph_size_for(u32::MAX, 8), None);
+
+    let font = Font::load(FONT);
+    assert_eq!(
+        glyph_size_for(font.width(), font.height()),
+        Some(font.header.glyph_size)
+    );
+
+    let mut font = font;
+    font.header.glyph_width = u32::MAX;
+    font.header.glyph_height = u32::MAX;
+    assert_eq!(
+        font.validate().next(),
+        Some(ValidationWarning::GlyphSizeMismatch {
+            expected: u32::MAX,
+            actual: 16,
+        })
+    );
+}
+
+#[test]
+fn display_glyph_flipped_y() {
+    let font = Font::load(FONT);
+
+    let mut normal = [[0; 8]; 16];
+    font.display_glyph('A', |bit, x, y| normal[y as usize][x as usize] = bit);
+
+    let mut flipped = [[0; 8]; 16];
+    font.display_glyph_flipped_y('A', |bit, x, y| flipped[y as usize][x as usize] = bit);
+
+    normal.reverse();
+    assert_eq!(normal, flipped);
+
+    assert_eq!(
+        font.display_glyph_flipped_y('A', |_, _, _| ()),
+        GlyphResult::Found
+    );
+    assert_eq!(
+        font.display_glyph_flipped_y('😀', |_, _, _| ()),
+        GlyphResult::Fallback
+    );
+}
+
+#[test]
+fn load_many() {
+    let blob: Vec<u8> = [FONT, PSF1_FONT, FONT].concat();
+    let fonts: Vec<_> = Font::load_many(&blob).collect();
+
+    assert_eq!(fonts.len(), 3);
+    for font in &fonts {
+        let font = font.as_ref().unwrap();
+        assert_eq!(
+            font.glyph_index(0x2126),
+            Font::load(FONT).glyph_index(0x2126)
+        );
+        assert_eq!(font.glyph_data(65), Font::load(FONT).glyph_data(65));
+    }
+    assert_eq!(fonts[1].as_ref().unwrap().header.format, Version::Psf1);
+
+    assert_eq!(Font::load_many(&[]).count(), 0);
+
+    let lens: Vec<usize> = fonts
+        .iter()
+        .map(|font| font.as_ref().unwrap().byte_len())
+        .collect();
+    assert_eq!(lens, [FONT.len(), PSF1_FONT.len(), FONT.len()]);
+
+    let broken: Vec<u8> = [FONT, &[0; 8], FONT].concat();
+    let mut fonts = Font::load_many(&broken);
+
+    assert!(fonts.next().unwrap().is_ok());
+    assert_eq!(fonts.next().unwrap().unwrap_err(), LoadError::BadMagic);
+    assert!(fonts.next().is_none());
+}
+
+#[test]
+fn huge_header() {
+    // The biggest glyphs there can be, and as many of them as the header can describe.
+    let mut raw = [0; 64];
+    raw[..32].copy_from_slice(&FONT[..32]);
+    raw[0x10..0x14].copy_from_slice(&u32::MAX.to_le_bytes());
+    raw[0x14..0x18].copy_from_slice(&(32 * 256u32).to_le_bytes());
+    raw[0x18..0x1c].copy_from_slice(&256u32.to_le_bytes());
+    raw[0x1c..0x20].copy_from_slice(&256u32.to_le_bytes());
+
+    // Only 32 bit targets overflow, and the font is truncated either way.
+    assert!(matches!(
+        Font::try_load(&raw).unwrap_err(),
+        LoadError::TruncatedGlyphData { .. } | LoadError::InvalidDimensions
+    ));
+
+    let mut font = Font::load(FONT);
+    font.header.length = u32::MAX;
+    assert_eq!(font.glyph_data(u32::MAX - 1), None);
+    assert!(!font.display_glyph_by_index(u32::MAX as usize - 1, |_, _, _| ()));
+}
+
+#[test]
+fn load_strict() {
+    let strict = LoadOptions::new().strict(true);
+
+    for raw in [FONT, PSF1_FONT] {
+        assert!(Font::try_load_with(raw, &strict).is_ok());
+        assert!(Font::try_load_with(raw, &LoadOptions::default()).is_ok());
+    }
+
+    let padded: Vec<u8> = [FONT, &[0; 3]].concat();
+    assert!(Font::try_load_with(&padded, &LoadOptions::new()).is_ok());
+    assert_eq!(
+        Font::try_load_with(&padded, &strict).unwrap_err(),
+        LoadError::TrailingData(3)
+    );
+
+    let blob: Vec<u8> = [PSF1_FONT, FONT].concat();
+    assert_eq!(
+        Font::try_load_with(&blob, &strict).unwrap_err(),
+        LoadError::TrailingData(FONT.len())
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn build_atlas() {
+    let font = Font::load(FONT);
+    let atlas = font.build_atlas(16);
+
+    assert_eq!((atlas.columns, atlas.rows), (16, 16));
+    assert_eq!((atlas.width(), atlas.height()), (128, 256));
+    assert_eq!(atlas.data.len(), 128 * 256);
+    assert_eq!(atlas.position(0x41), (8, 64));
+
+    let (left, top) = atlas.position(0x41);
+    font.display_glyph('A', |bit, x, y| {
+        let pixel = atlas.data[(top + y as u32) as usize * 128 + (left + x as u32) as usize];
+        assert_eq!(pixel, bit * 0xff);
+    });
+
+    // 256 glyphs don't fit evenly into 3 columns, so the last row has blank cells.
+    let atlas = font.build_atlas(3);
+    assert_eq!(atlas.rows, 86);
+    assert!(atlas.data[atlas.data.len() - 16..]
+        .iter()
+        .all(|pixel| *pixel == 0));
+    assert_eq!(font.build_atlas(0).columns, 1);
+
+    let wide = font.build_atlas(u32::MAX);
+    assert_eq!((wide.columns, wide.rows), (font.glyph_count(), 1));
+    assert_eq!(wide.width(), font.glyph_count() * 8);
+    assert_eq!(Font::empty().build_atlas(u32::MAX).columns, 1);
+
+    let mut zero = font.build_atlas(16);
+    zero.columns = 0;
+    assert_eq!(zero.position(3), (0, 48));
+}
+
+#[test]
+fn glyph_to_pbm() {
+    let font = Font::load(FONT);
+    let mut buf = [0; 64];
+
+    let len = font.glyph_to_pbm('A', &mut buf).unwrap();
+    assert_eq!(len, 8 + 16);
+    assert_eq!(&buf[..8], b"P4\n8 16\n");
+    assert_eq!(&buf[8..len], font.glyph_data(0x41).unwrap());
+
+    assert_eq!(font.glyph_to_pbm('A', &mut buf[..23]), None);
+    assert_eq!(font.glyph_to_pbm('A', &mut buf[..4]), None);
+
+    // Padding bits at the end of each row are cleared.
+    let glyphs = [0xff; 4];
+    let mut raw = [0; 64];
+    let len = FontBuilder::new(4, 4, &glyphs).write_to(&mut raw).unwrap();
+    let font = Font::load(&raw[..len]);
+
+    let len = font.glyph_to_pbm(0, &mut buf).unwrap();
+    assert_eq!(&buf[..len], b"P4\n4 4\n\xf0\xf0\xf0\xf0");
+}
+
+#[test]
+fn display_glyph_clipped() {
+    let font = Font::load(FONT);
+
+    let clip = Bounds {
+        x: 0,
+        y: 0,
+        w: 100,
+        h: 100,
+    };
+
+    let mut count = 0;
+    font.display_glyph_clipped('A', 96, 90, clip, |bit, x, y| {
+        assert!(clip.contains(x, y));
+        assert!(x >= 96 && y >= 90);
+
+        let mut expected = 0;
+        font.display_glyph('A', |original, ox, oy| {
+            if ox as u16 + 96 == x && oy as u16 + 90 == y {
+                expected = original;
+            }
+        });
+        assert_eq!(bit, expected);
+
+        count += 1;
+    });
+
+    // Only the top left 4x10 of the glyph is inside.
+    assert_eq!(count, 4 * 10);
+
+    let mut count = 0;
+    font.display_glyph_clipped(
+        'A',
+        u16::MAX - 2,
+        0,
+        Bounds {
+            w: u16::MAX,
+            ..clip
+        },
+        |_, _, _| {
+            count += 1;
+        },
+    );
+    assert_eq!(count, 2 * 16);
+
+    assert_eq!(
+        font.display_glyph_clipped('A', 0, 0, clip, |_, _, _| ()),
+        GlyphResult::Found
+    );
+    assert_eq!(
+        font.display_glyph_clipped('😀', 0, 0, clip, |_, _, _| ()),
+        GlyphResult::Fallback
+    );
+}
+
+#[test]
+fn index_cache() {
+    let mut font = Font::load(FONT);
+    let uncached = font.clone();
+
+    let latin = IndexCache::new(&font, 0..256);
+    font.set_index_cache(&latin);
+    for char in 0..0x3000 {
+        assert_eq!(font.glyph_index(char), uncached.glyph_index(char));
+    }
+
+    let mut cached = [[0; 8]; 16];
+    font.display_glyph('\u{2126}', |bit, x, y| cached[y as usize][x as usize] = bit);
+    let mut expected = [[0; 8]; 16];
+    uncached.display_glyph('\u{2126}', |bit, x, y| {
+        expected[y as usize][x as usize] = bit;
+    });
+    assert_eq!(cached, expected);
+
+    // Only the first `INDEX_CACHE_SIZE` characters are cached, the rest still work.
+    let punctuation = IndexCache::new(&uncached, 0x2000..0x3000);
+    font.set_index_cache(&punctuation);
+    assert_eq!(font.glyph_index(0x2126), uncached.glyph_index(0x2126));
+    assert_eq!(font.glyph_index(0xe9), uncached.glyph_index(0xe9));
+}
+
+#[test]
+fn unsorted_unicode_table() {
+    let glyphs = [0; 3 * 8];
+    let mut buf = [0; 128];
+    let len = FontBuilder::new(8, 8, &glyphs)
+        .unicode(&[('ž', 0), ('é', 1), ('ä', 2), ('é', 2)])
+        .write_to(&mut buf)
+        .unwrap();
+    let font = Font::load(&buf[..len]);
+
+    assert_eq!(font.glyph_index('ž' as u32), Some(0));
+    assert_eq!(font.glyph_index('ä' as u32), Some(2));
+    // The last mapping for a character wins.
+    assert_eq!(font.glyph_index('é' as u32), Some(2));
+    assert_eq!(font.glyph_index('ö' as u32), None);
+
+    assert!(font
+        .unicode
+        .as_ref()
+        .unwrap()
+        .windows(2)
+        .all(|pair| pair[0].0 < pair[1].0));
+}
+
+#[test]
+fn invalid_utf8_in_unicode_table() {
+    let glyphs = [0; 3 * 8];
+    let mut buf = [0; 128];
+    let len = FontBuilder::new(8, 8, &glyphs)
+        .unicode(&[('é', 0), ('€', 1), ('ü', 2)])
+        .write_to(&mut buf)
+        .unwrap();
+
+    let table = 32 + glyphs.len();
+    assert_eq!(
+        &buf[table..len],
+        &[0xc3, 0xa9, 0xff, 0xe2, 0x82, 0xac, 0xff, 0xc3, 0xbc, 0xff]
+    );
+
+    // An overlong 'A' and a surrogate, neither of which are real characters.
+    buf[table..table + 2].copy_from_slice(&[0xc1, 0x81]);
+    buf[table + 3..table + 6].copy_from_slice(&[0xed, 0xa0, 0x80]);
+    let font = Font::load(&buf[..len]);
+
+    assert!(font.unicode_entries().eq([('ü', 2)]));
+    assert_eq!(font.glyph_index('ü' as u32), Some(2));
+    assert_eq!(font.glyph_index(0xd800), None);
+}
+
+#[test]
+fn invalid_utf8_resync() {
+    let glyphs = [0; 3 * 8];
+    let mut buf = [0; 128];
+    let len = FontBuilder::new(8, 8, &glyphs)
+        .unicode(&[('x', 0)])
+        .write_to(&mut buf)
+        .unwrap();
+
+    // Lead bytes that are followed by the start of another character, instead of the rest of theirs.
+    let table = 32 + glyphs.len();
+    let raw: Vec<u8> = [
+        &buf[..table],
+        &[0xe2, 0xc3, 0xa9, 0xff],
+        &[0xc3, 0xc3, 0xb1, 0xff],
+        &[0xe2, b'A', 0xc3, 0xbc, 0xff],
+    ]
+    .concat();
+    assert!(len < raw.len());
+
+    let expected = [('A', 2), ('é', 0), ('ñ', 1), ('ü', 2)];
+    let parsed = Font::load(&raw);
+    assert!(parsed.unicode_entries().eq(expected));
+
+    let lazy = Font::try_load_lazy(&raw).unwrap();
+    let zero = Font::<0>::try_load_with_capacity(&raw).unwrap();
+    for font in [
+        &lazy.unicode_entries().collect::<Vec<_>>(),
+        &zero.unicode_entries().collect(),
+    ] {
+        let mut entries = font.clone();
+        entries.sort_unstable();
+        assert_eq!(entries, expected);
+    }
+
+    for char in ['é', 'ñ', 'ü'] {
+        assert_eq!(
+            lazy.glyph_index(char as u32),
+            parsed.glyph_index(char as u32)
+        );
+        assert_eq!(
+            zero.glyph_index(char as u32),
+            parsed.glyph_index(char as u32)
+        );
+    }
+
+    assert_eq!(StaticFont::entry_count(&raw), 4);
+    let entries = StaticFont::entries::<4>(&raw);
+    let embedded = StaticFont::new(&raw, &entries);
+    assert_eq!(entries, [(0x41, 2), (0xe9, 0), (0xf1, 1), (0xfc, 2)]);
+    assert_eq!(embedded.glyph_index('ñ' as u32), Some(1));
+
+    // A character that's cut off at the end of an entry is still dropped.
+    let raw: Vec<u8> = [&buf[..table], &[0xc3, 0xa9, 0xe2, 0x82, 0xff]].concat();
+    assert!(Font::load(&raw).unicode_entries().eq([('é', 0)]));
+}
+
+#[test]
+fn unicode_capacity() {
+    let font = Font::load(FONT);
+    let entries = font.unicode.as_ref().unwrap().len();
+
+    assert_eq!(
+        Font::<2>::try_load_with_capacity(FONT).unwrap_err(),
+        LoadError::UnicodeTableFull
+    );
+
+    let small: Font<'_, 512> = Font::try_load_with_capacity(FONT).unwrap();
+    assert!(entries <= 512);
+    assert_eq!(small.glyph_index(0x2126), font.glyph_index(0x2126));
+
+    // Fonts without a unicode table don't need any room at all.
+    let glyphs = [0; 8];
+    let mut buf = [0; 64];
+    let len = FontBuilder::new(8, 8, &glyphs).write_to(&mut buf).unwrap();
+    assert!(Font::<0>::try_load_with_capacity(&buf[..len]).is_ok());
+}
+
+#[test]
+fn write_glyph_art() {
+    let glyphs = [0b1000_0000, 0b0110_0000, 0b0000_0000];
+    let mut buf = [0; 64];
+    let len = FontBuilder::new(3, 3, &glyphs).write_to(&mut buf).unwrap();
+    let font = Font::load(&buf[..len]);
+
+    let mut art = String::new();
+    font.write_glyph_art(0, &mut art, '\u{2588}', '.').unwrap();
+    assert_eq!(art, "\u{2588}..\n.\u{2588}\u{2588}\n...\n");
+
+    // Writers that fail part of the way through stop getting written to.
+    let mut small = SliceWriter([0; 5], 0);
+    assert!(font.write_glyph_art(0, &mut small, '#', '.').is_err());
+    assert_eq!(&small.0, b"#..\n.");
+}
+
+/// A writer with a fixed amount of room, for checking what happens when writing fails.
+struct SliceWriter([u8; 5], usize);
+
+impl core::fmt::Write for SliceWriter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let target = self
+            .0
+            .get_mut(self.1..self.1 + s.len())
+            .ok_or(core::fmt::Error)?;
+        target.copy_from_slice(s.as_bytes());
+        self.1 += s.len();
+        Ok(())
+    }
+}
+
+#[test]
+fn validate() {
+    assert_eq!(Font::load(FONT).validate().count(), 0);
+    assert_eq!(Font::load(PSF1_FONT).validate().count(), 0);
+
+    let glyphs = [0; 4 * 8];
+    let mut buf = [0; 128];
+    let len = FontBuilder::new(8, 8, &glyphs)
+        .unicode(&[])
+        .write_to(&mut buf)
+        .unwrap();
+    assert!(Font::load(&buf[..len])
+        .validate()
+        .eq([ValidationWarning::EmptyUnicodeTable]));
+    assert!(Font::try_load_lazy(&buf[..len])
+        .unwrap()
+        .validate()
+        .eq([ValidationWarning::EmptyUnicodeTable]));
+    assert!(Font::<0>::try_load_with_capacity(&buf[..len])
+        .unwrap()
+        .validate()
+        .eq([ValidationWarning::EmptyUnicodeTable]));
+    assert_eq!(Font::try_load_lazy(FONT).unwrap().validate().count(), 0);
+    assert_eq!(
+        Font::<0>::try_load_with_capacity(FONT)
+            .unwrap()
+            .validate()
+            .count(),
+        0
+    );
+
+    // Only one unicode entry for two glyphs, and glyphs twice as big as they need to be.
+    buf[0x10..0x14].copy_from_slice(&2u32.to_le_bytes());
+    buf[0x14..0x18].copy_from_slice(&16u32.to_le_bytes());
+    buf[0x18..0x1c].copy_from_slice(&16u32.to_le_bytes());
+    let mut font = Font::load(&buf[..len - 3]);
+    font.header.glyph_height = 8;
+    assert!(font.validate().eq([
+        ValidationWarning::GlyphSizeMismatch {
+            expected: 8,
+            actual: 16
+        },
+        ValidationWarning::UnicodeEntriesMismatch {
+            expected: 2,
+            actual: 1
+        },
+        ValidationWarning::EmptyUnicodeTable,
+    ]));
+}
+
+#[test]
+fn measure_str() {
+    let font = Font::load(FONT);
+
+    assert_eq!(font.measure_str(""), (0, 0));
+    assert_eq!(font.measure_str("abc"), (24, 16));
+    assert_eq!(font.measure_str("ab\nabcd\n"), (32, 48));
+    assert_eq!(font.measure_str("a\tb"), (72, 16));
+
+    let text = "hello\nworld!";
+    let mut right = 0;
+    font.display_str(text, |_, x, _| right = right.max(x + 1));
+    assert_eq!(font.measure_str(text).0, right);
+
+    // Both stop at the same place once a line is too long.
+    for text in ["\t".repeat(1100), "A".repeat(9000) + "\t"] {
+        assert_eq!(font.measure_str(&text).0, u16::MAX);
+        assert_eq!(font.display_str(&text, |_, _, _| ()).0, u16::MAX);
+    }
+}
+
+#[test]
+fn display_str_combining() {
+    let font = Font::load(FONT);
+    // The font doesn't have any real combining marks, so an underscore stands in for one.
+    let is_combining = |char| char == '_';
+
+    let mut base = [[0; 16]; 16];
+    font.display_str("e", |bit, x, y| base[y as usize][x as usize] |= bit);
+    font.display_glyph('_', |bit, x, y| base[y as usize][x as usize] |= bit);
+
+    let mut combined = [[0; 16]; 16];
+    assert_eq!(
+        font.display_str_combining("e_", is_combining, |bit, x, y| {
+            combined[y as usize][x as usize] |= bit;
+        }),
+        (8, 0)
+    );
+    assert_eq!(base, combined);
+
+    // Without anything before them, combining characters get their own cell.
+    assert_eq!(
+        font.display_str_combining("_a\n_", is_combining, |_, _, _| ()),
+        (8, 16)
+    );
+    assert_eq!(font.display_str("e_", |_, _, _| ()), (16, 0));
+}
+
+#[test]
+fn display_str_spaced() {
+    let font = Font::load(FONT);
+
+    assert_eq!(
+        font.display_str_spaced("ab\ncd", 2, 3, |_, _, _| ()),
+        (20, 19)
+    );
+    assert_eq!(font.display_str_spaced("a\tb", 2, 0, |_, _, _| ()), (90, 0));
+    assert_eq!(
+        font.display_str_spaced("ab", 0, 0, |_, _, _| ()),
+        font.display_str("ab", |_, _, _| ())
+    );
+
+    // The second glyph starts 2 pixels after the first one ends.
+    let mut max_x = 0;
+    font.display_str_spaced("\u{2588}\u{2588}", 2, 0, |bit, x, _| {
+        if bit == 1 {
+            max_x = max_x.max(x);
+        }
+    });
+    assert_eq!(max_x, 17);
+
+    // Negative spacing can't move the cursor back past the start.
+    assert_eq!(
+        font.display_str_spaced("ab\nc", -20, -20, |_, _, _| ()),
+        (0, 0)
+    );
+    assert_eq!(font.display_str_spaced("abc", -4, 0, |_, _, _| ()), (12, 0));
+}
+
+#[test]
+fn glyph_bytes() {
+    let font = Font::load(FONT);
+
+    assert_eq!(font.glyph_bytes('A'), font.glyph_data(0x41));
+    assert_eq!(
+        font.glyph_bytes('\u{2126}'),
+        font.glyph_data(font.glyph_index(0x2126).unwrap() as u32)
+    );
+    assert_eq!(font.glyph_bytes('\u{3042}'), None);
+
+    // Characters sharing a glyph get the same bytes.
+    for index in 0..font.glyph_count() as usize {
+        let mut chars = font.chars_for_glyph(index);
+        if let (Some(first), Some(second)) = (chars.next(), chars.next()) {
+            assert_eq!(font.glyph_bytes(first), font.glyph_bytes(second));
+        }
+    }
+}
+
+#[test]
+fn try_display_glyph() {
+    let font = Font::load(FONT);
+
+    let mut expected = [[0; 8]; 16];
+    font.display_glyph('A', |bit, x, y| expected[y as usize][x as usize] = bit);
+    let mut actual = [[0; 8]; 16];
+    assert_eq!(
+        font.try_display_glyph('A', |bit, x, y| actual[y as usize][x as usize] = bit),
+        Ok(GlyphResult::Found)
+    );
+    assert_eq!(expected, actual);
+
+    assert_eq!(
+        font.try_display_glyph(-1, |_, _, _| ()),
+        Err(RenderError::InvalidCharacter)
+    );
+
+    // A font with no glyphs loads fine, but has nothing to display.
+    let mut raw = [0; 32];
+    raw.copy_from_slice(&FONT[..32]);
+    raw[0xc] = 0;
+    raw[0x10..0x14].copy_from_slice(&0u32.to_le_bytes());
+    let empty = Font::load(&raw);
+    assert_eq!(
+        empty.try_display_glyph('A', |_, _, _| ()),
+        Err(RenderError::GlyphDataTruncated)
+    );
+}
+
+#[test]
+fn display_glyph_region() {
+    let font = Font::load(FONT);
+
+    let mut glyph = [[0; 8]; 16];
+    font.display_glyph('A', |bit, x, y| glyph[y as usize][x as usize] = bit);
+
+    let region = Bounds {
+        x: 2,
+        y: 5,
+        w: 3,
+        h: 4,
+    };
+    let mut visited = Vec::new();
+    assert_eq!(
+        font.display_glyph_region('A', region, |bit, x, y| {
+            assert_eq!(bit, glyph[y as usize][x as usize]);
+            visited.push((x, y));
+        }),
+        GlyphResult::Found
+    );
+
+    let expected: Vec<(u8, u8)> = (5..9).flat_map(|y| (2..5).map(move |x| (x, y))).collect();
+    assert_eq!(visited, expected);
+
+    // Anything past the edge of the glyph is clipped off.
+    let region = Bounds {
+        x: 6,
+        y: 14,
+        w: 100,
+        h: 100,
+    };
+    let mut count = 0;
+    font.display_glyph_region('A', region, |_, _, _| count += 1);
+    assert_eq!(count, 2 * 2);
+
+    let mut full = [[0; 8]; 16];
+    let region = Bounds {
+        x: 0,
+        y: 0,
+        w: 8,
+        h: 16,
+    };
+    font.display_glyph_region('A', region, |bit, x, y| full[y as usize][x as usize] = bit);
+    assert_eq!(full, glyph);
+}
+
+#[test]
+fn diff() {
+    let font = Font::load(FONT);
+    assert_eq!(font.diff(&font).count(), 0);
+    assert_eq!(font.diff(&Font::load(PSF1_FONT)).count(), 0);
+
+    let mut old = [0; 64];
+    let len = FontBuilder::new(8, 1, &[0, 1, 2])
+        .unicode(&[('é', 1), ('ß', 2)])
+        .write_to(&mut old)
+        .unwrap();
+    let old = Font::load(&old[..len]);
+
+    let mut new = [0; 64];
+    let len = FontBuilder::new(8, 1, &[0, 9, 2, 3])
+        .unicode(&[('é', 2), ('ñ', 3)])
+        .write_to(&mut new)
+        .unwrap();
+    let new = Font::load(&new[..len]);
+
+    let diff: Vec<DiffEntry> = old.diff(&new).collect();
+    assert_eq!(
+        diff,
+        [
+            DiffEntry::Glyph(1),
+            DiffEntry::Glyph(3),
+            DiffEntry::Mapping {
+                char: 'ß',
+                old: Some(2),
+                new: None
+            },
+            DiffEntry::Mapping {
+                char: 'é',
+                old: Some(1),
+                new: Some(2)
+            },
+            DiffEntry::Mapping {
+                char: 'ñ',
+                old: None,
+                new: Some(3)
+            },
+        ]
+    );
+}
+
+#[test]
+fn display_glyph_by_index() {
+    let font = Font::load(FONT);
+    let index = font.glyph_index('╝' as u32).unwrap();
+
+    let mut expected = [[0; 8]; 16];
+    font.display_glyph('╝', |bit, x, y| expected[y as usize][x as usize] = bit);
+
+    let mut glyph = [[0; 8]; 16];
+    assert!(font.display_glyph_by_index(index, |bit, x, y| glyph[y as usize][x as usize] = bit));
+    assert_eq!(glyph, expected);
+
+    let count = font.glyph_count() as usize;
+    assert!(font.display_glyph_by_index(count - 1, |_, _, _| ()));
+    assert!(!font.display_glyph_by_index(count, |_, _, _| panic!("out of bounds")));
+    assert!(!font.display_glyph_by_index(usize::MAX, |_, _, _| panic!("out of bounds")));
+}
+
+#[test]
+fn empty_font() {
+    const EMPTY: Font = Font::empty();
+
+    assert_eq!(EMPTY.glyph_count(), 0);
+    assert_eq!(EMPTY.glyphs().count(), 0);
+    assert_eq!(
+        EMPTY.display_glyph('A', |_, _, _| panic!("nothing should be displayed")),
+        GlyphResult::Missing
+    );
+    assert!(EMPTY.is_blank('A'));
+    assert_eq!(EMPTY.glyph_bounds('A'), None);
+    assert_eq!(
+        EMPTY.try_display_glyph('A', |_, _, _| ()),
+        Err(RenderError::GlyphDataTruncated)
+    );
+    assert_eq!(EMPTY.measure_str("hi"), (16, 16));
+    assert!(EMPTY.validate().next().is_none());
+
+    let mut cache: GlyphCache<2> = GlyphCache::new();
+    assert_eq!(cache.get_or_render(&EMPTY, 'A'), None);
+
+    assert_eq!(Font::default().header, EMPTY.header);
+}
+
+#[test]
+fn from_parts() {
+    let header = Header::parse(FONT.first_chunk::<HEADER_SIZE>().unwrap()).unwrap();
+    assert_eq!(header, Font::load(FONT).header);
+    assert_eq!(header.glyph_data_len(), 256 * 16);
+
+    let psf1 = Header::parse(PSF1_FONT.first_chunk::<HEADER_SIZE>().unwrap()).unwrap();
+    assert_eq!(psf1.format, Version::Psf1);
+    assert_eq!(Header::parse(&[0; HEADER_SIZE]), Err(LoadError::BadMagic));
+
+    let glyphs_end = header.size as usize + header.glyph_data_len();
+    let glyphs = &FONT[header.size as usize..glyphs_end];
+    let table = &FONT[glyphs_end..];
+
+    let font: Font = Font::from_parts(header, glyphs, table).unwrap();
+    let loaded = Font::load(FONT);
+    assert_eq!(font.glyph_index(0x2126), loaded.glyph_index(0x2126));
+    assert_eq!(font.glyph_data(0x41), loaded.glyph_data(0x41));
+    assert_eq!(font.sequence_index(&['A']), loaded.sequence_index(&['A']));
+
+    assert_eq!(
+        Font::<16>::from_parts(header, &glyphs[..100], table).unwrap_err(),
+        LoadError::TruncatedGlyphData {
+            expected: 256 * 16,
+            actual: 100
+        }
+    );
+}
+
+#[test]
+#[cfg(feature = "cp437")]
+fn cp437_index() {
+    let glyphs = [0; 256 * 8];
+    let mut buf = [0; 32 + 256 * 8];
+    let len = FontBuilder::new(8, 8, &glyphs).write_to(&mut buf).unwrap();
+    let font = Font::load(&buf[..len]);
+
+    assert_eq!(font.cp437_index('A'), Some(0x41));
+    assert_eq!(font.cp437_index('\u{255d}'), Some(0xbc));
+    assert_eq!(font.cp437_index('\u{263a}'), Some(0x01));
+    assert_eq!(font.cp437_index('\u{a0}'), Some(0xff));
+    assert_eq!(font.cp437_index('\u{2302}'), Some(0x7f));
+    assert_eq!(font.cp437_index('\u{3042}'), None);
+
+    // Fonts that stop early don't have the glyphs past their end.
+    let len = FontBuilder::new(8, 8, &glyphs[..4 * 8])
+        .write_to(&mut buf)
+        .unwrap();
+    let short = Font::load(&buf[..len]);
+    assert_eq!(short.cp437_index('\u{255d}'), None);
+    assert_eq!(short.cp437_index('\u{263a}'), Some(0x01));
+
+    // Fonts with a unicode table use it instead.
+    let font = Font::load(FONT);
+    assert_eq!(font.cp437_index('\u{2126}'), font.glyph_index(0x2126));
+    assert_eq!(font.cp437_index('A'), Some(0x41));
+}
+
+#[test]
+fn try_from() {
+    let font: Font = FONT.try_into().unwrap();
+    assert_eq!(
+        font.glyph_index(0x2126),
+        Font::load(FONT).glyph_index(0x2126)
+    );
+
+    let small = Font::<512>::try_from(FONT).unwrap();
+    assert_eq!(small.glyph_count(), 256);
+
+    assert_eq!(
+        Font::<512>::try_from(&FONT[..16]).unwrap_err(),
+        LoadError::TooShort
+    );
+}
+
+#[test]
+fn format_detection() {
+    assert_eq!(detect_format(FONT), Format::Psf2);
+    assert_eq!(detect_format(PSF1_FONT), Format::Psf1);
+    assert_eq!(
+        detect_format(include_bytes!("../test.psfu.gz")),
+        Format::Gzip
+    );
+
+    assert_eq!(detect_format(&FONT[..3]), Format::Unknown);
+    assert_eq!(detect_format(&PSF1_FONT[..2]), Format::Psf1);
+    assert_eq!(detect_format(&[]), Format::Unknown);
+    assert_eq!(detect_format(b"hello"), Format::Unknown);
+}
+
+#[test]
+fn glyph_cache() {
+    let font = Font::load(FONT);
+    let mut cache = GlyphCache::<2>::new();
+    assert!(cache.is_empty());
+
+    let mut expected = [0; 8 * 16];
+    font.display_glyph('A', |bit, x, y| expected[y as usize * 8 + x as usize] = bit);
+    assert_eq!(cache.get_or_render(&font, 'A').unwrap(), expected);
+    assert_eq!(cache.get_or_render(&font, 'A').unwrap(), expected);
+    assert_eq!(cache.len(), 1);
+
+    // 'A' was used more recently than 'B', so 'B' is the one thrown out for 'C'.
+    cache.get_or_render(&font, 'B');
+    cache.get_or_render(&font, 'A');
+    cache.get_or_render(&font, 'C');
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get_or_render(&font, 'A').unwrap(), expected);
+
+    let mut c = [0; 8 * 16];
+    font.display_glyph('C', |bit, x, y| c[y as usize * 8 + x as usize] = bit);
+    assert_eq!(cache.get_or_render(&font, 'C').unwrap(), c);
+
+    cache.clear();
+    assert!(cache.is_empty());
+
+    // Glyphs that are too big, and caches with no room, just don't cache anything.
+    assert_eq!(GlyphCache::<2, 64>::new().get_or_render(&font, 'A'), None);
+    assert_eq!(GlyphCache::<0>::new().get_or_render(&font, 'A'), None);
+}
+
+#[test]
+fn display_glyph_pair() {
+    let font = Font::load(FONT);
+
+    let mut pair = [[0; 16]; 16];
+    let results = font.display_glyph_pair('A', '\u{3042}', |bit, x, y| {
+        pair[y as usize][x as usize] = bit;
+    });
+    assert_eq!(results, (GlyphResult::Found, GlyphResult::Fallback));
+
+    let mut expected = [[0; 16]; 16];
+    font.display_glyph('A', |bit, x, y| expected[y as usize][x as usize] = bit);
+    font.display_glyph('?', |bit, x, y| expected[y as usize][x as usize + 8] = bit);
+
+    assert_eq!(pair, expected);
+}
+
+#[test]
+fn coverage() {
+    let font = Font::load(FONT);
+
+    assert_eq!(font.coverage(""), 0);
+    assert_eq!(font.coverage("hello"), 5);
+    assert_eq!(font.coverage("\u{2126} \u{3042}\u{3044}"), 2);
+}
+
+#[cfg(feature = "embedded-graphics")]
+#[test]
+fn draw_glyph() {
+    use core::convert::Infallible;
+
+    use embedded_graphics_core::{pixelcolor::BinaryColor, prelude::*};
+
+    struct Display([[BinaryColor; 16]; 24]);
+
+    impl OriginDimensions for Display {
+        fn size(&self) -> Size {
+            Size::new(16, 24)
+        }
+    }
+
+    impl DrawTarget for Display {
+        type Color = BinaryColor;
+        type Error = Infallible;
+
+        fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            for Pixel(point, color) in pixels {
+                let (x, y) = (usize::try_from(point.x), usize::try_from(point.y));
+                self.0[y.unwrap()][x.unwrap()] = color;
+            }
+
+            Ok(())
+        }
+    }
+
+    let font = Font::load(FONT);
+    let mut display = Display([[BinaryColor::Off; 16]; 24]);
+
+    assert_eq!(
+        font.draw_glyph('A', Point::new(4, 3), BinaryColor::On, &mut display),
+        Ok(GlyphResult::Found)
+    );
+    font.display_glyph('A', |bit, x, y| {
+        let expected = if bit == 1 {
+            BinaryColor::On
+        } else {
+            BinaryColor::Off
+        };
+        assert_eq!(display.0[y as usize + 3][x as usize + 4], expected);
+    });
+
+    let mut fallback = Display([[BinaryColor::Off; 16]; 24]);
+    assert_eq!(
+        font.draw_glyph('😀', Point::zero(), BinaryColor::On, &mut fallback),
+        Ok(GlyphResult::Fallback)
+    );
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn glyph_image() {
+    let font = Font::load(FONT);
+
+    let image = font.glyph_image('A');
+    assert_eq!(image.dimensions(), (8, 16));
+    font.display_glyph('A', |bit, x, y| {
+        assert_eq!(image.get_pixel(x as u32, y as u32).0, [bit * 0xff]);
+    });
+    assert_eq!(font.glyph_image('😀'), font.glyph_image('?'));
+
+    let atlas = font.build_atlas(16);
+    let data = atlas.data.clone();
+    let image = atlas.into_image().unwrap();
+    assert_eq!(image.dimensions(), (128, 256));
+    assert_eq!(image.into_raw(), data);
+
+    let mut atlas = font.build_atlas(16);
+    atlas.data.pop();
+    assert_eq!(atlas.into_image(), None);
+}